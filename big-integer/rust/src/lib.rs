@@ -1,469 +1,4547 @@
 use std::cmp::Ordering;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Binary, Display, Formatter, LowerHex, Octal, UpperHex};
+use std::hash::{Hash, Hasher};
 use std::ops;
+use std::str::FromStr;
 
-#[derive(Clone, PartialEq)]
+const LIMB_DIGITS: usize = 9;
+const LIMB_BASE: u64 = 1_000_000_000;
+// from_scientific 结果的位数上限，防止指数过大时分配失控
+const MAX_SCIENTIFIC_DIGITS: usize = 1 << 24;
+// 超过该位数时 gcd 改用二进制 GCD，省去欧几里得算法每步的长除法
+const BINARY_GCD_MIN_DIGITS: usize = 64;
+
+// value 为 10^9 进制的 limb，低位在前，最高 limb 不为零；零为 [0] 且取正号
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BigInt {
     positive: bool,
-    value: Vec<i8>,
+    value: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit(char),
+    InvalidSign,
+    NotAnInteger,
+    ExponentOutOfRange,
+}
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit(c) => write!(f, "invalid digit found in string: {:?}", c),
+            ParseBigIntError::InvalidSign => write!(f, "invalid sign found in string"),
+            ParseBigIntError::NotAnInteger => write!(f, "number in string is not an integer"),
+            ParseBigIntError::ExponentOutOfRange => write!(f, "exponent in string is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryFromBigIntError {
+    OutOfRange,
+    Negative,
+}
+
+impl Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryFromBigIntError::OutOfRange => write!(f, "out of range integral type conversion attempted"),
+            TryFromBigIntError::Negative => write!(f, "negative value can't be converted to an unsigned type"),
+        }
+    }
 }
 
+impl std::error::Error for TryFromBigIntError {}
+
+// 除不尽时商的取整方式，Half* 的“半”指余数恰为除数的一半
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    Truncate,
+    Floor,
+    Ceil,
+    // 远离零
+    HalfUp,
+    // 取偶数
+    HalfEven,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitLimitError {
+    pub max_digits: usize,
+}
+
+impl Display for DigitLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "result would exceed {} decimal digits", self.max_digits)
+    }
+}
+
+impl std::error::Error for DigitLimitError {}
+
 impl Display for BigInt {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let sign = match self.positive {
-            true => "",
-            false => "-"
+        use std::fmt::Write;
+
+        // 最高 limb 不补零，其余 limb 各占 9 位
+        let mut digits = String::with_capacity(self.value.len() * LIMB_DIGITS);
+        let mut limbs = self.value.iter().rev();
+        write!(digits, "{}", limbs.next().unwrap())?;
+        for limb in limbs {
+            write!(digits, "{:09}", limb)?;
+        }
+        f.pad_integral(self.positive, "", &digits)
+    }
+}
+
+impl LowerHex for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.magnitude_str_radix(16);
+        f.pad_integral(!self.is_negative(), "0x", &digits)
+    }
+}
+
+impl UpperHex for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.magnitude_str_radix(16).to_ascii_uppercase();
+        f.pad_integral(!self.is_negative(), "0x", &digits)
+    }
+}
+
+impl Octal for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.magnitude_str_radix(8);
+        f.pad_integral(!self.is_negative(), "0o", &digits)
+    }
+}
+
+impl Binary for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.magnitude_str_radix(2);
+        f.pad_integral(!self.is_negative(), "0b", &digits)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BigInt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BigInt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::try_new(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl num_traits::Zero for BigInt {
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        BigInt::is_zero(self)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl num_traits::One for BigInt {
+    fn one() -> Self {
+        BigInt::one()
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<BigInt> for num_bigint::BigInt {
+    fn from(value: BigInt) -> Self {
+        let digits: Vec<u8> = value.digits().collect();
+        let magnitude = num_bigint::BigUint::from_radix_be(&digits, 10).unwrap();
+        let sign = match value.positive {
+            true => num_bigint::Sign::Plus,
+            false => num_bigint::Sign::Minus,
         };
-        write!(f, "{}{}", sign,
-               self.value
-                   .iter()
-                   .fold(String::new(), |acc, &p| acc + &p.to_string()))
+        // 零会被规整为 NoSign
+        num_bigint::BigInt::from_biguint(sign, magnitude)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(value: num_bigint::BigInt) -> Self {
+        let (sign, digits) = value.to_radix_be(10);
+        let mut integer: Self = digits.into_iter().collect();
+        integer.positive = sign != num_bigint::Sign::Minus;
+        integer
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new(s)
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(v: u128) -> Self {
+        Self::from_magnitude(true, v)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(v: i128) -> Self {
+        Self::from_magnitude(v >= 0, v.unsigned_abs())
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(v: u64) -> Self {
+        Self::from(v as u128)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(v: i64) -> Self {
+        Self::from(v as i128)
+    }
+}
+
+impl From<u32> for BigInt {
+    fn from(v: u32) -> Self {
+        Self::from(v as u64)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(v: i32) -> Self {
+        Self::from(v as i64)
+    }
+}
+
+impl TryFrom<BigInt> for u64 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(v: BigInt) -> Result<Self, Self::Error> {
+        if !v.positive { return Err(TryFromBigIntError::Negative); }
+        v.magnitude_u64().ok_or(TryFromBigIntError::OutOfRange)
+    }
+}
+
+impl TryFrom<BigInt> for i64 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(v: BigInt) -> Result<Self, Self::Error> {
+        let magnitude = v.magnitude_u64().ok_or(TryFromBigIntError::OutOfRange)?;
+        if v.positive {
+            i64::try_from(magnitude).map_err(|_| TryFromBigIntError::OutOfRange)
+        } else {
+            0i64.checked_sub_unsigned(magnitude).ok_or(TryFromBigIntError::OutOfRange)
+        }
     }
 }
 
 impl PartialOrd for BigInt {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
         let ordering = self.positive.cmp(&other.positive);
         if ordering != Ordering::Equal {
-            return Some(ordering);
+            return ordering;
         }
 
-        let ordering = self.value.len().cmp(&other.value.len());
-        if ordering != Ordering::Equal {
-            return Some(ordering);
+        let ordering = self.cmp_magnitude(other);
+        // 同为负数时绝对值越大越小
+        match self.positive {
+            true => ordering,
+            false => ordering.reverse(),
         }
+    }
+}
+
+impl PartialEq<i64> for BigInt {
+    fn eq(&self, other: &i64) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<i64> for BigInt {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        Some(self.cmp(&Self::from(*other)))
+    }
+}
+
+impl PartialEq<BigInt> for i64 {
+    fn eq(&self, other: &BigInt) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<BigInt> for i64 {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+// 右侧按 try_new 解析，非法字符串视为不相等
+impl PartialEq<str> for BigInt {
+    fn eq(&self, other: &str) -> bool {
+        Self::try_new(other).is_ok_and(|v| *self == v)
+    }
+}
+
+impl PartialEq<&str> for BigInt {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
 
-        Some(self.value.cmp(&other.value))
+impl Default for BigInt {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl Hash for BigInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let positive = self.positive || self.value == [0];
+        positive.hash(state);
+        self.value.hash(state);
     }
 }
 
 impl BigInt {
     pub fn new(v: &str) -> Self {
-        if v.len() == 0 { panic!("IllegalArgument") }
+        Self::try_new(v).unwrap_or_else(|e| panic!("IllegalArgument: {}", e))
+    }
+
+    pub fn try_new(v: &str) -> Result<Self, ParseBigIntError> {
+        let bytes = v.as_bytes();
+        if bytes.is_empty() { return Err(ParseBigIntError::Empty); }
+        if bytes.contains(&b'_') {
+            return Self::try_new(Self::strip_separators(v, 10)?.as_str());
+        }
 
         let mut positive = true;
         let mut begin_index = 0;
-        match v.chars().nth(0).unwrap() {
-            '-' => {
+        match bytes[0] {
+            b'-' => {
                 positive = false;
                 begin_index += 1;
             }
-            '+' => begin_index += 1,
+            b'+' => begin_index += 1,
             _ => {}
         };
-        while begin_index < v.len() && v.chars().nth(begin_index).unwrap() == '0' {
+        if begin_index == bytes.len() { return Err(ParseBigIntError::InvalidSign); }
+        while begin_index < bytes.len() && bytes[begin_index] == b'0' {
             begin_index += 1;
         }
-        if begin_index == v.len() { begin_index -= 1; }
+        if begin_index == bytes.len() { begin_index -= 1; }
+
+        for (i, &b) in bytes.iter().enumerate().skip(begin_index) {
+            if !b.is_ascii_digit() {
+                return Err(match b {
+                    b'+' | b'-' => ParseBigIntError::InvalidSign,
+                    // 前面均为 ASCII，i 必在字符边界上
+                    _ => ParseBigIntError::InvalidDigit(v[i..].chars().next().unwrap()),
+                });
+            }
+        }
 
+        // 从最低位起每 9 位压成一个 limb
         let mut integer = Self {
             positive,
-            value: Vec::with_capacity(v.len() - begin_index),
+            value: bytes[begin_index..]
+                .rchunks(LIMB_DIGITS)
+                .map(|chunk| chunk.iter().fold(0, |acc, &b| acc * 10 + (b - b'0') as u32))
+                .collect(),
+        };
+
+        integer.set_zero_positive();
+        Ok(integer)
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            positive: true,
+            value: vec![0],
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            positive: true,
+            value: vec![1],
+        }
+    }
+
+    // 直接构造 exp / 9 个零 limb，最高 limb 为 10^(exp % 9)
+    pub fn pow10(exp: usize) -> Self {
+        let mut value = vec![0; exp / LIMB_DIGITS + 1];
+        value[exp / LIMB_DIGITS] = 10u32.pow((exp % LIMB_DIGITS) as u32);
+        Self {
+            positive: true,
+            value,
+        }
+    }
+
+    // '_' 只能出现在两个数字之间
+    fn strip_separators(v: &str, radix: u32) -> Result<String, ParseBigIntError> {
+        let bytes = v.as_bytes();
+        let is_digit = |i: usize| i < bytes.len() && (bytes[i] as char).is_digit(radix);
+        for (i, &b) in bytes.iter().enumerate() {
+            if b == b'_' && (i == 0 || !is_digit(i - 1) || !is_digit(i + 1)) {
+                return Err(ParseBigIntError::InvalidDigit('_'));
+            }
+        }
+        Ok(v.replace('_', ""))
+    }
+
+    // 仅去除首尾的 ASCII 空白，数字之间的空白仍视为非法
+    pub fn from_str_trimmed(v: &str) -> Result<Self, ParseBigIntError> {
+        Self::try_new(v.trim_matches(|c: char| c.is_ascii_whitespace()))
+    }
+
+    // 形如 1.5e3 的科学计数法，结果必须是整数
+    pub fn from_scientific(s: &str) -> Result<Self, ParseBigIntError> {
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], Self::parse_exponent(&s[i + 1..])?),
+            None => (s, 0),
+        };
+        // 拼接整数与小数部分后 try_new 会接受原本非法的分隔符位置，如 1_.5
+        if mantissa.contains('_') { return Err(ParseBigIntError::InvalidDigit('_')); }
+
+        let (integral, fractional) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let integer = Self::try_new(format!("{}{}", integral, fractional).as_str())?;
+        let scale = exp.saturating_sub(fractional.len() as i64);
+        if scale >= 0 {
+            // 零左移后仍为零，其余结果的位数不得超过上限
+            if integer.is_zero() {
+                return Ok(integer);
+            }
+            return match usize::try_from(scale).ok().and_then(|scale| scale.checked_add(integer.num_digits())) {
+                Some(digits) if digits <= MAX_SCIENTIFIC_DIGITS => Ok(integer.shift_decimal_left(scale as usize)),
+                _ => Err(ParseBigIntError::ExponentOutOfRange),
+            };
+        }
+
+        // 右移舍去的各位必须全为零
+        let n = scale.unsigned_abs() as usize;
+        match (0..n.min(integer.num_digits())).all(|i| integer.digit_at(i) == Some(0)) {
+            true => Ok(integer.shift_decimal_right(n)),
+            false => Err(ParseBigIntError::NotAnInteger),
+        }
+    }
+
+    // 指数只接受可选符号加 ASCII 数字，不允许分隔符
+    fn parse_exponent(s: &str) -> Result<i64, ParseBigIntError> {
+        if s.is_empty() { return Err(ParseBigIntError::Empty); }
+        let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+        if digits.is_empty() { return Err(ParseBigIntError::InvalidSign); }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(match c {
+                '+' | '-' => ParseBigIntError::InvalidSign,
+                _ => ParseBigIntError::InvalidDigit(c),
+            });
+        }
+        s.parse().map_err(|_| ParseBigIntError::ExponentOutOfRange)
+    }
+
+    fn from_magnitude(positive: bool, mut magnitude: u128) -> Self {
+        if magnitude == 0 {
+            return Self::zero();
+        }
+
+        let mut value = Vec::with_capacity(5);
+        while magnitude > 0 {
+            value.push((magnitude % LIMB_BASE as u128) as u32);
+            magnitude /= LIMB_BASE as u128;
+        }
+
+        Self { positive, value }
+    }
+
+    pub fn to_i64_saturating(&self) -> i64 {
+        match (self.positive, self.magnitude_u64()) {
+            (true, Some(magnitude)) => i64::try_from(magnitude).unwrap_or(i64::MAX),
+            (false, Some(magnitude)) => 0i64.checked_sub_unsigned(magnitude).unwrap_or(i64::MIN),
+            (true, None) => i64::MAX,
+            (false, None) => i64::MIN,
+        }
+    }
+
+    pub fn to_u64_saturating(&self) -> u64 {
+        if !self.positive {
+            return 0;
+        }
+        self.magnitude_u64().unwrap_or(u64::MAX)
+    }
+
+    // 超出 f64 范围时为带符号的无穷大
+    // 交给 str::parse 做正确舍入；过长时保留前 768 位，其后非零则补一位 1 作为粘滞位，
+    // 足以区分舍入的中点
+    pub fn to_f64(&self) -> f64 {
+        let significant = self.num_digits().min(768);
+        let mut mantissa: String = self.digits()
+            .take(significant)
+            .map(|d| (b'0' + d) as char)
+            .collect();
+        let mut exp = self.num_digits() - significant;
+        if self.digits().skip(significant).any(|d| d != 0) {
+            mantissa.push('1');
+            exp -= 1;
+        }
+        let sign = if self.positive { "" } else { "-" };
+        format!("{}{}e{}", sign, mantissa, exp)
+            .parse()
+            .unwrap()
+    }
+
+    // 向零截断，NaN 与无穷大返回 None
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        // value = mantissa * 2^(exponent - 1075)
+        let (mantissa, shift) = if exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), exponent - 1075)
         };
-        for i in begin_index..v.len() {
-            let c = v.chars().nth(i).unwrap();
-            if c < '0' || c > '9' { panic!("IllegalArgument") }
-            integer.value.push(c.to_digit(10).unwrap() as i8);
+
+        let magnitude = if shift >= 0 {
+            BigInt::from(mantissa) * BigInt::from(2).pow(shift as u32)
+        } else if shift > -64 {
+            BigInt::from(mantissa >> -shift)
+        } else {
+            BigInt::zero()
+        };
+        Some(if value < 0.0 { -magnitude } else { magnitude })
+    }
+
+    fn magnitude_u64(&self) -> Option<u64> {
+        self.value.iter().rev().try_fold(0u64, |acc, &limb| {
+            acc.checked_mul(LIMB_BASE)?.checked_add(limb as u64)
+        })
+    }
+
+    fn set_zero_positive(&mut self) {
+        if self.value == [0] {
+            self.positive = true;
         }
+    }
+
+    pub fn abs(&self) -> Self {
+        self.clone().into_abs()
+    }
+
+    // 忽略符号比较绝对值，最高 limb 不为零，先比 limb 数再从高位逐个比较
+    pub fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        Self::cmp_limbs(&self.value, &other.value)
+    }
+
+    fn cmp_limbs(a: &[u32], b: &[u32]) -> Ordering {
+        a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == [0]
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.positive && !self.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        !self.positive && !self.is_zero()
+    }
+
+    // 10^9 为偶数，奇偶只取决于最低 limb
+    pub fn is_even(&self) -> bool {
+        self.value[0].is_multiple_of(2)
+    }
+
+    pub fn is_odd(&self) -> bool {
+        !self.is_even()
+    }
+
+    pub fn num_digits(&self) -> usize {
+        (self.value.len() - 1) * LIMB_DIGITS + Self::limb_width(self.value[self.value.len() - 1])
+    }
+
+    // limb 不补零时的位数，零占一位
+    fn limb_width(limb: u32) -> usize {
+        limb.checked_ilog10().unwrap_or(0) as usize + 1
+    }
+
+    // 高位在前，不含符号
+    pub fn digits(&self) -> impl Iterator<Item=u8> + '_ {
+        let top = self.value.len() - 1;
+        self.value.iter().enumerate().rev().flat_map(move |(i, &limb)| {
+            let width = if i == top { Self::limb_width(limb) } else { LIMB_DIGITS };
+            (0..width as u32).rev().map(move |k| (limb / 10u32.pow(k) % 10) as u8)
+        })
+    }
 
+    // digits 高位在前，每位须在 0..=9，零总是取正号
+    pub fn from_digits(positive: bool, digits: &[u8]) -> Self {
+        let mut integer: Self = digits.iter().copied().collect();
+        integer.positive = positive;
         integer.set_zero_positive();
         integer
     }
 
-    fn set_zero_positive(&mut self) {
-        if self.value.len() == 1 && self.value[0] == 0 {
-            self.positive = true;
+    // 返回符号和十进制各位，高位在前，零的符号为正
+    pub fn into_parts(self) -> (bool, Vec<i8>) {
+        (self.positive, self.digits().map(|d| d as i8).collect())
+    }
+
+    pub fn digit_sum(&self) -> u64 {
+        self.digits().map(u64::from).sum()
+    }
+
+    /// Builds a `BigInt` from its sign and decimal digits without validating them.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be non-empty, most-significant digit first, with every digit
+    /// in `0..=9` and no leading zeros (zero is exactly `[0]`), and zero must have
+    /// `positive == true`. Other operations rely on this normalized form.
+    pub unsafe fn from_raw_parts(positive: bool, value: Vec<i8>) -> Self {
+        debug_assert!(!value.is_empty() && value.iter().all(|d| (0..=9).contains(d)));
+        debug_assert!(value.len() == 1 || value[0] != 0);
+        debug_assert!(positive || value != [0]);
+        Self { positive, value: Self::to_limbs(&value) }
+    }
+
+    // i 从最低位开始计数
+    pub fn digit_at(&self, i: usize) -> Option<u8> {
+        if i >= self.num_digits() {
+            return None;
+        }
+        Some((self.value[i / LIMB_DIGITS] / 10u32.pow((i % LIMB_DIGITS) as u32) % 10) as u8)
+    }
+
+    pub fn signum(&self) -> i8 {
+        if self.is_zero() {
+            0
+        } else if self.positive {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    pub fn clamp(self, low: Self, high: Self) -> Self {
+        debug_assert!(low <= high);
+        if self < low {
+            low
+        } else if self > high {
+            high
+        } else {
+            self
+        }
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if !(2..=36).contains(&radix) { panic!("radix must be in 2..=36") }
+
+        let digits = self.magnitude_str_radix(radix);
+        match self.is_negative() {
+            true => format!("-{}", digits),
+            false => digits,
+        }
+    }
+
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        if !(2..=36).contains(&radix) { panic!("radix must be in 2..=36") }
+        if radix == 10 {
+            return Self::try_new(s);
+        }
+        if s.is_empty() { return Err(ParseBigIntError::Empty); }
+        if s.contains('_') {
+            return Self::from_str_radix(Self::strip_separators(s, radix)?.as_str(), radix);
+        }
+
+        let (positive, digits) = match s.as_bytes()[0] {
+            b'-' => (false, &s[1..]),
+            b'+' => (true, &s[1..]),
+            _ => (true, s),
+        };
+        if digits.is_empty() { return Err(ParseBigIntError::InvalidSign); }
+
+        let base = Self::from(radix);
+        let mut integer = Self::zero();
+        for c in digits.chars() {
+            let d = match c.to_digit(radix) {
+                Some(d) => d,
+                None => return Err(match c {
+                    '+' | '-' => ParseBigIntError::InvalidSign,
+                    _ => ParseBigIntError::InvalidDigit(c),
+                }),
+            };
+            integer *= base.clone();
+            integer += Self::from(d);
+        }
+
+        integer.positive = positive;
+        integer.set_zero_positive();
+        Ok(integer)
+    }
+
+    pub fn to_bytes_be(&self) -> (bool, Vec<u8>) {
+        (self.positive, self.to_radix_digits(256))
+    }
+
+    pub fn from_bytes_be(positive: bool, bytes: &[u8]) -> Self {
+        let base = Self::from(256u32);
+        let mut integer = Self::zero();
+        for &b in bytes {
+            integer *= base.clone();
+            integer += Self::from(b as u32);
+        }
+
+        integer.positive = positive;
+        integer.set_zero_positive();
+        integer
+    }
+
+    pub fn to_bytes_le(&self) -> (bool, Vec<u8>) {
+        let (positive, mut bytes) = self.to_bytes_be();
+        bytes.reverse();
+        (positive, bytes)
+    }
+
+    pub fn from_bytes_le(positive: bool, bytes: &[u8]) -> Self {
+        let bytes: Vec<u8> = bytes.iter().rev().cloned().collect();
+        Self::from_bytes_be(positive, bytes.as_slice())
+    }
+
+    // 绝对值的 2^32 进制各位，低位在前，零为空
+    pub fn to_u32_limbs(&self) -> Vec<u32> {
+        if self.is_zero() {
+            return vec![];
+        }
+
+        let (_, bytes) = self.to_bytes_le();
+        bytes.chunks(4)
+            .map(|chunk| chunk.iter().rev().fold(0u32, |acc, &b| acc << 8 | b as u32))
+            .collect()
+    }
+
+    pub fn from_u32_limbs(positive: bool, limbs: &[u32]) -> Self {
+        let base = Self::from(1u64 << 32);
+        let mut integer = Self::zero();
+        for &limb in limbs.iter().rev() {
+            integer *= base.clone();
+            integer += Self::from(limb);
+        }
+
+        integer.positive = positive;
+        integer.set_zero_positive();
+        integer
+    }
+
+    pub fn bits(&self) -> u64 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        let (_, bytes) = self.to_bytes_be();
+        (bytes.len() as u64 - 1) * 8 + (8 - bytes[0].leading_zeros() as u64)
+    }
+
+    // 绝对值二进制表示中 1 的个数
+    pub fn count_ones(&self) -> u64 {
+        self.to_bytes_le().1.iter().map(|b| b.count_ones() as u64).sum()
+    }
+
+    // 绝对值二进制表示中末尾零的个数，零返回 None
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // 每次剥离 31 位，避免整体转换为二进制
+        let mut zeros = 0u64;
+        let (mut magnitude, mut remainder) = self.div_rem_small(1 << 31);
+        while remainder == 0 {
+            zeros += 31;
+            (magnitude, remainder) = magnitude.div_rem_small(1 << 31);
+        }
+        Some(zeros + remainder.trailing_zeros() as u64)
+    }
+
+    // 负数按补码解释，与 num_bigint::BigInt::bit 一致
+    pub fn bit(&self, index: u64) -> bool {
+        // -m 的补码即 m - 1 按位取反
+        if self.is_negative() {
+            return !(self.abs() - Self::one()).bit(index);
+        }
+
+        let (_, bytes) = self.to_bytes_le();
+        bytes.get((index / 8) as usize).is_some_and(|&b| b >> (index % 8) & 1 == 1)
+    }
+
+    // 负数按补码修改，符号位保持不变
+    pub fn set_bit(&mut self, index: u64, value: bool) {
+        let byte = (index / 8) as usize;
+        let len = self.to_bytes_le().1.len().max(byte + 1) + 1;
+        let mut bytes = self.to_twos_complement_le(len);
+        match value {
+            true => bytes[byte] |= 1 << (index % 8),
+            false => bytes[byte] &= !(1 << (index % 8)),
+        }
+        *self = Self::from_twos_complement_le(bytes);
+    }
+
+    pub fn is_power_of_two(&self) -> bool {
+        if !self.is_positive() {
+            return false;
+        }
+
+        let (_, bytes) = self.to_bytes_be();
+        bytes[0].is_power_of_two() && bytes[1..].iter().all(|&b| b == 0)
+    }
+
+    // 非正数返回 1
+    pub fn next_power_of_two(&self) -> Self {
+        if !self.is_positive() {
+            return Self::one();
+        }
+        if self.is_power_of_two() {
+            return self.clone();
+        }
+        Self::one() << self.bits() as u32
+    }
+
+    // 模 2^bits 落到 [0, 2^bits)，相当于 bits 位无符号整数的回绕
+    pub fn wrapping_to_bits(&self, bits: u32) -> Self {
+        self.clone().rem_euclid(Self::one() << bits)
+    }
+
+    // 按 bits 位补码解释，落到 [-2^(bits-1), 2^(bits-1))
+    pub fn wrapping_signed_to_bits(&self, bits: u32) -> Self {
+        if bits == 0 { panic!("signed width must be at least 1 bit") }
+
+        let unsigned = self.wrapping_to_bits(bits);
+        match unsigned.bit(bits as u64 - 1) {
+            true => unsigned - (Self::one() << bits),
+            false => unsigned,
+        }
+    }
+
+    // 按补码逐字节运算，负数视为无限长的符号位扩展
+    fn bitwise(&self, rhs: &Self, op: fn(u8, u8) -> u8) -> Self {
+        let len = self.to_bytes_le().1.len().max(rhs.to_bytes_le().1.len()) + 1;
+        let bytes = self.to_twos_complement_le(len)
+            .into_iter()
+            .zip(rhs.to_twos_complement_le(len))
+            .map(|(a, b)| op(a, b))
+            .collect();
+        Self::from_twos_complement_le(bytes)
+    }
+
+    // 小端补码表示，len 需留出符号位
+    fn to_twos_complement_le(&self, len: usize) -> Vec<u8> {
+        let (_, mut bytes) = self.to_bytes_le();
+        bytes.resize(len, 0);
+        if self.is_negative() {
+            Self::negate_twos_complement(&mut bytes);
+        }
+        bytes
+    }
+
+    fn from_twos_complement_le(mut bytes: Vec<u8>) -> Self {
+        let negative = bytes.last().is_some_and(|&b| b & 0x80 != 0);
+        if negative {
+            Self::negate_twos_complement(&mut bytes);
+        }
+        Self::from_bytes_le(!negative, bytes.as_slice())
+    }
+
+    fn negate_twos_complement(bytes: &mut [u8]) {
+        let mut carry = true;
+        for b in bytes.iter_mut() {
+            *b = !*b;
+            if carry {
+                let (v, overflow) = b.overflowing_add(1);
+                *b = v;
+                carry = overflow;
+            }
+        }
+    }
+
+    fn magnitude_str_radix(&self, radix: u32) -> String {
+        self.to_radix_digits(radix)
+            .into_iter()
+            .map(|d| std::char::from_digit(d as u32, radix).unwrap())
+            .collect()
+    }
+
+    // 尾数固定保留 significant 位有效数字，多余的位直接截断，不足时补零
+    pub fn to_scientific(&self, significant: usize) -> String {
+        if significant == 0 { panic!("significant digits must be positive") }
+        if self.is_zero() {
+            return "0e0".to_string();
+        }
+
+        let mut s = String::with_capacity(significant + 24);
+        if self.is_negative() {
+            s.push('-');
+        }
+        let mut mantissa = self.digits().chain(std::iter::repeat(0)).take(significant);
+        s.push((b'0' + mantissa.next().unwrap()) as char);
+        if significant > 1 {
+            s.push('.');
+            s.extend(mantissa.map(|d| (b'0' + d) as char));
+        }
+        s.push_str(format!("e{}", self.num_digits() - 1).as_str());
+        s
+    }
+
+    // 从最低位起每 group_size 位插入一个分隔符
+    pub fn to_grouped_string(&self, separator: char, group_size: usize) -> String {
+        if group_size == 0 { panic!("group size must be positive") }
+
+        let len = self.num_digits();
+        let mut s = String::with_capacity(len + len / group_size * separator.len_utf8() + 1);
+        if self.is_negative() {
+            s.push('-');
+        }
+        for (i, d) in self.digits().enumerate() {
+            if i > 0 && (len - i).is_multiple_of(group_size) {
+                s.push(separator);
+            }
+            s.push((b'0' + d) as char);
+        }
+        s
+    }
+
+    // 绝对值在 radix 进制下的各位，高位在前
+    fn to_radix_digits(&self, radix: u32) -> Vec<u8> {
+        let mut magnitude = self.abs();
+        let mut digits = vec![];
+        while !magnitude.is_zero() {
+            let (quotient, remainder) = magnitude.div_rem_small(radix);
+            digits.push(remainder as u8);
+            magnitude = quotient;
+        }
+        if digits.is_empty() {
+            digits.push(0);
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn into_abs(self) -> Self {
+        Self {
+            positive: true,
+            value: self.value,
+        }
+    }
+
+    // 十进制各位（高位在前）每 9 位压成一个 limb，低位在前，可能带有高位的零 limb
+    fn to_limbs(digits: &[i8]) -> Vec<u32> {
+        digits.rchunks(LIMB_DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |acc, &d| acc * 10 + d as u32))
+            .collect()
+    }
+
+    // limb < 10^9，单步 product + x * y + carry < 10^18 + 2 * 10^9，不会溢出 u64
+    fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut product = vec![0u32; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &y) in b.iter().enumerate() {
+                let p = product[i + j] as u64 + x as u64 * y as u64 + carry;
+                product[i + j] = (p % LIMB_BASE) as u32;
+                carry = p / LIMB_BASE;
+            }
+            product[i + b.len()] = carry as u32;
+        }
+        product
+    }
+
+    pub fn increment(&mut self) {
+        if self.positive {
+            self.increment_magnitude();
+        } else {
+            self.decrement_magnitude();
+        }
+    }
+
+    pub fn decrement(&mut self) {
+        if self.is_zero() {
+            self.positive = false;
+            self.value[0] = 1;
+        } else if self.positive {
+            self.decrement_magnitude();
+        } else {
+            self.increment_magnitude();
+        }
+    }
+
+    pub fn mul_small(&self, factor: u32) -> Self {
+        if factor == 0 || self.is_zero() {
+            return Self::zero();
+        }
+
+        // limb * factor + carry < 10^9 * 2^32 + 2^33，不会溢出 u64
+        let mut value = Vec::with_capacity(self.value.len() + 2);
+        let mut carry = 0u64;
+        for &limb in self.value.iter() {
+            let p = limb as u64 * factor as u64 + carry;
+            value.push((p % LIMB_BASE) as u32);
+            carry = p / LIMB_BASE;
+        }
+        while carry > 0 {
+            value.push((carry % LIMB_BASE) as u32);
+            carry /= LIMB_BASE;
+        }
+
+        Self {
+            positive: self.positive,
+            value,
+        }
+    }
+
+    // 整 limb 的部分在低位补零 limb，余下不足 9 位的部分乘以 10^(n % 9)
+    pub fn shift_decimal_left(&self, n: usize) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        let mut shifted = self.mul_small(10u32.pow((n % LIMB_DIGITS) as u32));
+        shifted.value.splice(0..0, std::iter::repeat_n(0, n / LIMB_DIGITS));
+        shifted
+    }
+
+    // 向零截断
+    pub fn shift_decimal_right(&self, n: usize) -> Self {
+        if n >= self.num_digits() {
+            return Self::zero();
+        }
+
+        let shifted = Self {
+            positive: self.positive,
+            value: self.value[n / LIMB_DIGITS..].to_vec(),
+        };
+        shifted.div_rem_small(10u32.pow((n % LIMB_DIGITS) as u32)).0
+    }
+
+    // 左闭右开，start >= end 时为空
+    pub fn range(start: Self, end: Self) -> impl Iterator<Item=Self> {
+        let mut current = start;
+        std::iter::from_fn(move || {
+            if current >= end {
+                return None;
+            }
+            let next = current.clone();
+            current.increment();
+            Some(next)
+        })
+    }
+
+    fn increment_magnitude(&mut self) {
+        for limb in self.value.iter_mut() {
+            if (*limb as u64) < LIMB_BASE - 1 {
+                *limb += 1;
+                return;
+            }
+            *limb = 0;
+        }
+        // 全为 999999999，进位一个 limb
+        self.value.push(1);
+    }
+
+    // 调用方保证绝对值不为零
+    fn decrement_magnitude(&mut self) {
+        for limb in self.value.iter_mut() {
+            if *limb > 0 {
+                *limb -= 1;
+                break;
+            }
+            *limb = (LIMB_BASE - 1) as u32;
+        }
+        if self.value.len() > 1 && self.value[self.value.len() - 1] == 0 {
+            self.value.pop();
+        }
+        self.set_zero_positive();
+    }
+
+    // 去除高位的零 limb 并令零取正号，用于直接修改 limb 之后恢复不变式
+    pub fn normalize(&mut self) {
+        self.trim_zero();
+        self.set_zero_positive();
+    }
+
+    fn trim_zero(&mut self) {
+        while self.value.last() == Some(&0) {
+            self.value.pop();
+        }
+        // 全为零时保留一个 limb
+        if self.value.is_empty() {
+            self.value.push(0);
+            self.positive = true;
+        }
+    }
+}
+
+impl ops::Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.value == [0] {
+            return self;
+        }
+
+        Self {
+            positive: !self.positive,
+            value: self.value,
+        }
+    }
+}
+
+impl ops::Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl ops::Add<BigInt> for BigInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.positive != rhs.positive {
+            return if self.positive {
+                self - (-rhs)
+            } else {
+                rhs - (-self)
+            };
+        }
+
+        // 以下同号
+        let (mut longer, mut shorter) = (&self, &rhs);
+        if self.value.len() < rhs.value.len() {
+            longer = &rhs;
+            shorter = &self;
+        }
+
+        // 每个 limb 的中间值至多 2 * (10^9 - 1) + 1，在 u32 范围内
+        let mut value = Vec::with_capacity(longer.value.len() + 1);
+        let mut carry = 0;
+        for (i, &limb) in longer.value.iter().enumerate() {
+            let s = limb + shorter.value.get(i).copied().unwrap_or(0) + carry;
+            carry = (s as u64 >= LIMB_BASE) as u32;
+            value.push(s - carry * LIMB_BASE as u32);
+        }
+        if carry > 0 {
+            value.push(carry);
+        }
+
+        Self {
+            positive: self.positive,
+            value,
+        }
+    }
+}
+
+impl ops::Sub<BigInt> for BigInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self == rhs {
+            return Self::zero();
+        }
+
+        if self.positive != rhs.positive {
+            return if self.positive {
+                self + -rhs
+            } else {
+                -(rhs + -self)
+            };
+        }
+
+        if self.cmp_magnitude(&rhs) == Ordering::Less {
+            return Self {
+                positive: !self.positive,
+                value: (rhs.into_abs() - self.into_abs()).value,
+            };
+        } else if !self.positive {
+            return Self {
+                positive: false,
+                value: (self.into_abs() - rhs.into_abs()).value,
+            };
+        };
+
+        // 以下 self > rhs >= 0，逐 limb 相减并向高位借位
+        let mut diff = self;
+        Self::sub_limbs_in_place(&mut diff.value, &rhs.value);
+        diff.trim_zero();
+        diff
+    }
+}
+
+impl ops::Mul<BigInt> for BigInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+
+        // 单个 limb 的操作数走 O(n) 的快速路径，其余按 limb 做竖式乘法
+        let mut product = match (self.value.len(), rhs.value.len()) {
+            (1, _) => rhs.mul_small(self.value[0]),
+            (_, 1) => self.mul_small(rhs.value[0]),
+            _ => {
+                let mut product = Self {
+                    positive: true,
+                    value: Self::mul_limbs(&self.value, &rhs.value),
+                };
+                product.trim_zero();
+                product
+            }
+        };
+
+        // 两个非零数的积不为零，直接设置符号
+        product.positive = self.positive == rhs.positive;
+        product
+    }
+}
+
+impl BigInt {
+    pub fn div_rem(self, rhs: Self) -> (Self, Self) {
+        if rhs.is_zero() { panic!("divisor can't be 0") }
+        if self.cmp_magnitude(&rhs) == Ordering::Less {
+            return (Self::zero(), self);
+        }
+
+        // 商的符号由两者决定，余数与被除数同号
+        let (quotient, remainder) = self.divmod_impl(&rhs);
+        let mut quotient = Self {
+            positive: self.positive == rhs.positive,
+            value: quotient,
+        };
+        quotient.set_zero_positive();
+        let mut remainder = Self {
+            positive: self.positive,
+            value: remainder,
+        };
+        remainder.set_zero_positive();
+        (quotient, remainder)
+    }
+
+    pub fn is_divisible_by(&self, divisor: &Self) -> bool {
+        if divisor.is_zero() { panic!("divisor can't be 0") }
+
+        // 除数含因子 2 或 5 时，被除数的末位必须也含有
+        let last = divisor.value[0] % 10;
+        let self_last = self.value[0] % 10;
+        if last.is_multiple_of(2) && !self_last.is_multiple_of(2)
+            || last.is_multiple_of(5) && !self_last.is_multiple_of(5) {
+            return false;
+        }
+        if divisor.value.len() == 1 {
+            return self.div_rem_small(divisor.value[0]).1 == 0;
+        }
+        if self.cmp_magnitude(divisor) == Ordering::Less {
+            return self.is_zero();
+        }
+        self.divmod_impl(divisor).1 == [0]
+    }
+
+    // 绝对值按 limb 做竖式除法（Knuth 算法 D），要求 |self| >= |rhs|，
+    // 返回商和余数的各 limb，均已去除高位的零
+    fn divmod_impl(&self, rhs: &Self) -> (Vec<u32>, Vec<u32>) {
+        if rhs.value.len() == 1 {
+            let (quotient, remainder) = self.div_rem_small(rhs.value[0]);
+            return (quotient.value, vec![remainder]);
+        }
+
+        // 同乘 scale 使除数最高 limb 不小于 10^9 / 2，此时每步试商至多偏大 2
+        let scale = (LIMB_BASE / (rhs.value[rhs.value.len() - 1] as u64 + 1)) as u32;
+        let divisor = rhs.mul_small(scale).value;
+        let mut remainder = self.mul_small(scale).value;
+        remainder.push(0);
+
+        let n = divisor.len();
+        let (top, second) = (divisor[n - 1] as u64, divisor[n - 2] as u64);
+        let mut quotient = vec![0; remainder.len() - n];
+        for j in (0..quotient.len()).rev() {
+            // 用余数最高两个 limb 试商，再借助除数次高 limb 修正
+            let numerator = remainder[j + n] as u64 * LIMB_BASE + remainder[j + n - 1] as u64;
+            let (mut q, mut r) = (numerator / top, numerator % top);
+            while q >= LIMB_BASE || q * second > r * LIMB_BASE + remainder[j + n - 2] as u64 {
+                q -= 1;
+                r += top;
+                if r >= LIMB_BASE {
+                    break;
+                }
+            }
+
+            // 减去 q * divisor，不够减时说明 q 仍大了 1，加回一次 divisor
+            let (mut carry, mut borrow) = (0u64, 0u64);
+            for i in 0..n {
+                let p = q * divisor[i] as u64 + carry;
+                carry = p / LIMB_BASE;
+                let d = remainder[i + j] as u64 + LIMB_BASE - p % LIMB_BASE - borrow;
+                remainder[i + j] = (d % LIMB_BASE) as u32;
+                borrow = 1 - d / LIMB_BASE;
+            }
+            match (remainder[j + n] as u64).checked_sub(carry + borrow) {
+                Some(d) => remainder[j + n] = d as u32,
+                None => {
+                    q -= 1;
+                    remainder[j + n] = 0;
+                    Self::add_limbs_in_place(&mut remainder[j..j + n], &divisor);
+                }
+            }
+            quotient[j] = q as u32;
+        }
+
+        let mut quotient = Self { positive: true, value: quotient };
+        quotient.trim_zero();
+        remainder.truncate(n);
+        let remainder = Self { positive: true, value: remainder }.div_rem_small(scale).0;
+        (quotient.value, remainder.value)
+    }
+
+    // 要求 a >= b，不去除高位的零
+    fn sub_limbs_in_place(a: &mut [u32], b: &[u32]) {
+        let mut borrow = 0;
+        for (i, x) in a.iter_mut().enumerate() {
+            if i >= b.len() && borrow == 0 {
+                break;
+            }
+            let d = *x as u64 + LIMB_BASE - b.get(i).copied().unwrap_or(0) as u64 - borrow;
+            *x = (d % LIMB_BASE) as u32;
+            borrow = 1 - d / LIMB_BASE;
+        }
+    }
+
+    // a 与 b 等长，舍弃最高位的进位
+    fn add_limbs_in_place(a: &mut [u32], b: &[u32]) {
+        let mut carry = 0;
+        for (x, &y) in a.iter_mut().zip(b) {
+            let s = *x as u64 + y as u64 + carry;
+            *x = (s % LIMB_BASE) as u32;
+            carry = s / LIMB_BASE;
+        }
+    }
+
+    fn div_rem_euclid(self, rhs: Self) -> (Self, Self) {
+        let (quotient, remainder) = self.div_rem(rhs.clone());
+        if !remainder.is_negative() {
+            return (quotient, remainder);
+        }
+
+        // 余数调整到 [0, |rhs|)
+        match rhs.is_positive() {
+            true => (quotient - Self::one(), remainder + rhs),
+            false => (quotient + Self::one(), remainder - rhs),
+        }
+    }
+
+    pub fn div_euclid(self, rhs: Self) -> Self {
+        self.div_rem_euclid(rhs).0
+    }
+
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        self.div_rem_euclid(rhs).1
+    }
+
+    // 同 rem_euclid，已在 [0, modulus) 内时直接返回
+    pub fn reduce_mod(self, modulus: &Self) -> Self {
+        if !modulus.is_positive() { panic!("modulus must be positive") }
+        if !self.is_negative() && self < *modulus {
+            return self;
+        }
+        self.rem_euclid(modulus.clone())
+    }
+
+    pub fn div_floor(self, rhs: Self) -> Self {
+        let positive = rhs.positive;
+        let (quotient, remainder) = self.div_rem(rhs);
+        match !remainder.is_zero() && remainder.positive != positive {
+            true => quotient - Self::one(),
+            false => quotient,
+        }
+    }
+
+    pub fn div_ceil(self, rhs: Self) -> Self {
+        let positive = rhs.positive;
+        let (quotient, remainder) = self.div_rem(rhs);
+        match !remainder.is_zero() && remainder.positive == positive {
+            true => quotient + Self::one(),
+            false => quotient,
+        }
+    }
+
+    pub fn div_round(self, rhs: Self, mode: RoundingMode) -> Self {
+        let positive = self.positive == rhs.positive;
+        let divisor = rhs.abs();
+        let (quotient, remainder) = self.div_rem(rhs);
+        if remainder.is_zero() {
+            return quotient;
+        }
+
+        // 截断后的商向零靠拢，需要时朝真实商的方向调整一位
+        let half = remainder.abs().mul_small(2).cmp(&divisor);
+        let away = match mode {
+            RoundingMode::Truncate => false,
+            RoundingMode::Floor => !positive,
+            RoundingMode::Ceil => positive,
+            RoundingMode::HalfUp => half != Ordering::Less,
+            RoundingMode::HalfEven => half == Ordering::Greater || half == Ordering::Equal && quotient.is_odd(),
+        };
+        match (away, positive) {
+            (false, _) => quotient,
+            (true, true) => quotient + Self::one(),
+            (true, false) => quotient - Self::one(),
+        }
+    }
+
+    // 面向非负数，差为负时返回 None
+    pub fn checked_sub_nonneg(self, rhs: Self) -> Option<Self> {
+        match self < rhs {
+            true => None,
+            false => Some(self - rhs),
+        }
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => Some(self.div_rem(rhs).0),
+        }
+    }
+
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => Some(self.div_rem(rhs).1),
+        }
+    }
+
+    // 商向零截断，余数取绝对值部分
+    pub fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        if divisor == 0 { panic!("divisor can't be 0") }
+
+        // remainder < divisor，故每个 limb 的商都小于 10^9，remainder * 10^9 + limb 不会溢出 u64
+        let divisor = divisor as u64;
+        let mut value = Vec::with_capacity(self.value.len());
+        let mut remainder = 0u64;
+        for &limb in self.value.iter().rev() {
+            let current = remainder * LIMB_BASE + limb as u64;
+            value.push((current / divisor) as u32);
+            remainder = current % divisor;
+        }
+        value.reverse();
+
+        let mut quotient = Self {
+            positive: self.positive,
+            value,
+        };
+        quotient.trim_zero();
+        (quotient, remainder as u32)
+    }
+
+    // try_add / try_sub / try_mul 在结果超过 max_digits 位时返回 Err
+    pub fn try_add(self, rhs: Self, max_digits: usize) -> Result<Self, DigitLimitError> {
+        // 同号相加时结果位数不少于较长的操作数，异号相加不会变长
+        if self.positive == rhs.positive && self.num_digits().max(rhs.num_digits()) > max_digits {
+            return Err(DigitLimitError { max_digits });
+        }
+        Self::limit_digits(self + rhs, max_digits)
+    }
+
+    pub fn try_sub(self, rhs: Self, max_digits: usize) -> Result<Self, DigitLimitError> {
+        self.try_add(-rhs, max_digits)
+    }
+
+    pub fn try_mul(self, rhs: Self, max_digits: usize) -> Result<Self, DigitLimitError> {
+        // 积的位数为 la + lb - 1 或 la + lb
+        if !self.is_zero() && !rhs.is_zero() && self.num_digits() + rhs.num_digits() - 1 > max_digits {
+            return Err(DigitLimitError { max_digits });
+        }
+        Self::limit_digits(self * rhs, max_digits)
+    }
+
+    // self + a * b，积仍需单独计算，累加走原地的 AddAssign
+    pub fn add_mul(mut self, a: &Self, b: &Self) -> Self {
+        self += a * b;
+        self
+    }
+
+    // coeffs 按次数升序，Horner 法从最高次项开始：acc = acc * x + c
+    pub fn eval_poly(coeffs: &[Self], x: &Self) -> Self {
+        coeffs.iter()
+            .rev()
+            .fold(Self::zero(), |acc, c| c.clone().add_mul(&acc, x))
+    }
+
+    fn limit_digits(value: Self, max_digits: usize) -> Result<Self, DigitLimitError> {
+        match value.num_digits() <= max_digits {
+            true => Ok(value),
+            false => Err(DigitLimitError { max_digits }),
+        }
+    }
+
+    // 0^0 取 1
+    pub fn pow(self, mut exp: u32) -> Self {
+        let mut result = Self::one();
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base.clone();
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.clone() * base;
+            }
+        }
+        result
+    }
+
+    // 结果超过 max_digits 位时返回 None，尽量不实际计算
+    pub fn checked_pow(self, exp: u32, max_digits: usize) -> Option<Self> {
+        // 0、±1 和零次幂的结果只有一位
+        if exp == 0 || self.value.len() == 1 && self.value[0] <= 1 {
+            return (max_digits >= 1).then(|| self.pow(exp));
+        }
+
+        // 位数为 floor(exp * log10|self|) + 1，log10 取前 17 位估算，留出误差余量
+        let lead = self.num_digits().min(17);
+        let mantissa = self.digits().take(lead).fold(0f64, |acc, d| acc * 10.0 + d as f64);
+        let log10 = mantissa.log10() + (self.num_digits() - lead) as f64;
+        if exp as f64 * log10 > max_digits as f64 + 1.0 {
+            return None;
+        }
+
+        // 余量内的边界情况实际计算，结果至多比上限多一两位
+        let result = self.pow(exp);
+        (result.num_digits() <= max_digits).then_some(result)
+    }
+
+    // 结果位数随 exp 线性增长，除 0、±1 外 exp 实际上不会超出 u32；取模请用 modpow
+    pub fn pow_big(self, exp: &Self) -> Self {
+        if !exp.positive { panic!("exponent can't be negative") }
+
+        let (_, bytes) = exp.to_bytes_le();
+        let bits = exp.bits();
+        let mut result = Self::one();
+        let mut base = self;
+        for i in 0..bits {
+            if bytes[(i / 8) as usize] >> (i % 8) & 1 == 1 {
+                result *= base.clone();
+            }
+            if i + 1 < bits {
+                base = base.clone() * base;
+            }
+        }
+        result
+    }
+
+    pub fn modpow(self, exp: &Self, modulus: &Self) -> Self {
+        let zero = Self::zero();
+        if modulus <= &zero { panic!("modulus must be positive") }
+        if !exp.positive { panic!("exponent can't be negative") }
+
+        let two = Self::new("2");
+        let mut base = self.reduce_mod(modulus);
+        let mut result = Self::one() % modulus.clone();
+        let mut exp = exp.clone();
+        while exp != zero {
+            if exp.is_odd() {
+                result = result * base.clone() % modulus.clone();
+            }
+            exp /= two.clone();
+            if exp != zero {
+                base = base.clone() * base % modulus.clone();
+            }
+        }
+        result
+    }
+
+    fn shl_bits(self, n: u32) -> Self {
+        self.mul_pow2(n)
+    }
+
+    // 算术右移向负无穷取整
+    fn shr_bits(self, n: u32) -> Self {
+        let (quotient, exact) = self.div_pow2_exact(n);
+        // 截断商已带符号，负数有余数时再减一
+        match self.is_negative() && !exact {
+            true => quotient - Self::one(),
+            false => quotient,
+        }
+    }
+
+    // n <= 31 时单遍 mul_small 乘以 2^n；更大的 n 每次 31 位逐遍相乘反而比乘以 2^n 慢
+    pub fn mul_pow2(&self, n: u32) -> Self {
+        match n <= 31 {
+            true => self.mul_small(1 << n),
+            false => self.clone() * Self::from(2u32).pow(n),
+        }
+    }
+
+    // 向零截断
+    pub fn div_pow2(&self, n: u32) -> Self {
+        self.div_pow2_exact(n).0
+    }
+
+    // 与 mul_pow2 相同，n <= 31 时单遍 div_rem_small，更大的 n 直接除以 2^n；同时记录是否整除
+    fn div_pow2_exact(&self, n: u32) -> (Self, bool) {
+        if n <= 31 {
+            let (quotient, remainder) = self.div_rem_small(1 << n);
+            return (quotient, remainder == 0);
+        }
+        // |self| < 10^d < 2^(10d/3) <= 2^n 时商为零，不必算出 2^n
+        if n as u64 > self.num_digits() as u64 * 10 / 3 {
+            return (Self::zero(), self.is_zero());
+        }
+
+        let (quotient, remainder) = self.clone().div_rem(Self::from(2u32).pow(n));
+        (quotient, remainder.is_zero())
+    }
+
+    pub fn factorial(n: u64) -> Self {
+        (2..=n).fold(Self::one(), |acc, i| acc * Self::from(i))
+    }
+
+    pub fn fibonacci(n: u64) -> Self {
+        Self::fibonacci_pair(n).0
+    }
+
+    // L(n) = F(n - 1) + F(n + 1) = 2F(n + 1) - F(n)
+    pub fn lucas(n: u64) -> Self {
+        let (f, g) = Self::fibonacci_pair(n);
+        g.mul_small(2) - f
+    }
+
+    // 快速倍增，返回 (F(n), F(n + 1))：
+    // F(2k) = F(k) * (2F(k + 1) - F(k))，F(2k + 1) = F(k)^2 + F(k + 1)^2
+    fn fibonacci_pair(n: u64) -> (Self, Self) {
+        if n == 0 {
+            return (Self::zero(), Self::one());
+        }
+
+        let (f, g) = Self::fibonacci_pair(n / 2);
+        let even = f.clone() * (g.mul_small(2) - f.clone());
+        let odd = f.clone() * f + g.clone() * g;
+        match n % 2 {
+            0 => (even, odd),
+            _ => (odd.clone(), even + odd),
+        }
+    }
+
+    pub fn binomial(n: u64, k: u64) -> Self {
+        if k > n {
+            return Self::zero();
+        }
+
+        // 每一步的结果都是 C(n - k + i, i)，整除不会丢精度
+        let k = k.min(n - k);
+        (1..=k).fold(Self::one(), |acc, i| acc * Self::from(n - k + i) / Self::from(i))
+    }
+
+    pub fn gcd(self, other: Self) -> Self {
+        if self.num_digits().max(other.num_digits()) > BINARY_GCD_MIN_DIGITS {
+            return self.gcd_binary(other);
+        }
+
+        let zero = Self::zero();
+        let (mut a, mut b) = (self.into_abs(), other.into_abs());
+        while b != zero {
+            let r = a % b.clone();
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    // Stein 算法，只用到减法与移位
+    pub fn gcd_binary(self, other: Self) -> Self {
+        let (mut a, mut b) = (self.into_abs(), other.into_abs());
+        let (a_zeros, b_zeros) = match (a.trailing_zeros(), b.trailing_zeros()) {
+            (None, _) => return b,
+            (_, None) => return a,
+            (Some(a_zeros), Some(b_zeros)) => (a_zeros, b_zeros),
+        };
+
+        // 公因子 2^shift 最后补回，此后 a、b 均为奇数
+        let shift = a_zeros.min(b_zeros) as u32;
+        a = a >> a_zeros as u32;
+        b = b >> b_zeros as u32;
+        loop {
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            b -= a.clone();
+            match b.trailing_zeros() {
+                None => return a << shift,
+                Some(zeros) => b = b >> zeros as u32,
+            }
+        }
+    }
+
+    pub fn lcm(self, other: Self) -> Self {
+        let zero = Self::zero();
+        if self == zero || other == zero {
+            return zero;
+        }
+
+        let (a, b) = (self.into_abs(), other.into_abs());
+        let gcd = a.clone().gcd(b.clone());
+        a / gcd * b
+    }
+
+    // 返回 (g, x, y)，满足 self * x + other * y == g 且 g >= 0
+    pub fn extended_gcd(self, other: Self) -> (Self, Self, Self) {
+        let (mut old_r, mut r) = (self, other);
+        let (mut old_s, mut s) = (Self::one(), Self::zero());
+        let (mut old_t, mut t) = (Self::zero(), Self::one());
+        while !r.is_zero() {
+            let (quotient, remainder) = old_r.div_rem(r.clone());
+            old_r = std::mem::replace(&mut r, remainder);
+            let next_s = old_s - quotient.clone() * s.clone();
+            old_s = std::mem::replace(&mut s, next_s);
+            let next_t = old_t - quotient * t.clone();
+            old_t = std::mem::replace(&mut t, next_t);
+        }
+
+        if old_r.is_negative() {
+            return (-old_r, -old_s, -old_t);
+        }
+        (old_r, old_s, old_t)
+    }
+
+    pub fn modinv(self, modulus: &Self) -> Option<Self> {
+        if !modulus.is_positive() { panic!("modulus must be positive") }
+
+        let (gcd, x, _) = self.extended_gcd(modulus.clone());
+        if gcd != Self::one() {
+            return None;
+        }
+
+        let inverse = x % modulus.clone();
+        match inverse.is_negative() {
+            true => Some(inverse + modulus.clone()),
+            false => Some(inverse),
+        }
+    }
+
+    pub fn is_probably_prime(&self, rounds: u32) -> bool {
+        if *self < 2 {
+            return false;
+        }
+        for p in [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            let p = Self::from(p);
+            if *self == p {
+                return true;
+            }
+            if (self.clone() % p).is_zero() {
+                return false;
+            }
+        }
+
+        // self - 1 = d * 2^s
+        let one = Self::one();
+        let two = Self::from(2u32);
+        let n_minus_one = self.clone() - one.clone();
+        let mut d = n_minus_one.clone();
+        let mut s = 0;
+        while d.is_even() {
+            d /= two.clone();
+            s += 1;
+        }
+
+        // 底数均匀取自 [2, self - 2]；没有 rand 时依次取 2, 3, 4, ...
+        #[cfg(feature = "rand")]
+        let mut rng = rand::thread_rng();
+        #[cfg(not(feature = "rand"))]
+        let range = self.clone() - Self::from(3u32);
+        'witness: for _round in 0..rounds {
+            #[cfg(feature = "rand")]
+            let a = Self::random_range(&two, &n_minus_one, &mut rng);
+            #[cfg(not(feature = "rand"))]
+            let a = Self::from(_round) % range.clone() + two.clone();
+            let mut x = a.modpow(&d, self);
+            if x == one || x == n_minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = x.clone() * x % self.clone();
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    // 均匀分布于 [0, 10^digits)
+    #[cfg(feature = "rand")]
+    pub fn random(digits: usize, rng: &mut impl rand::Rng) -> Self {
+        if digits == 0 {
+            return Self::zero();
+        }
+
+        (0..digits).map(|_| rng.gen_range(0..10)).collect()
+    }
+
+    // 均匀分布于 [low, high)
+    #[cfg(feature = "rand")]
+    pub fn random_range(low: &Self, high: &Self, rng: &mut impl rand::Rng) -> Self {
+        if low >= high { panic!("empty range") }
+
+        let span = high.clone() - low.clone();
+        loop {
+            let offset = Self::random(span.num_digits(), rng);
+            if offset < span {
+                return offset + low.clone();
+            }
+        }
+    }
+
+    pub fn sqrt(&self) -> Self {
+        if !self.positive { panic!("square root of negative number") }
+        let zero = Self::zero();
+        if *self == zero {
+            return zero;
+        }
+
+        // 初值 10^ceil(len/2) 不小于平方根，牛顿迭代单调递减
+        let mut x = Self::pow10(self.num_digits().div_ceil(2));
+
+        let two = Self::new("2");
+        loop {
+            let y = (x.clone() + self.clone() / x.clone()) / two.clone();
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    // 负数仅支持奇数次方根，结果向零截断
+    pub fn nth_root(&self, n: u32) -> Self {
+        if n == 0 { panic!("zeroth root is undefined") }
+        if n == 1 {
+            return self.clone();
+        }
+        if !self.positive {
+            if n.is_multiple_of(2) { panic!("even root of negative number") }
+            return -self.abs().nth_root(n);
+        }
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        // 与 sqrt 相同，初值 10^ceil(len/n) 不小于 n 次方根，牛顿迭代单调递减
+        let mut x = Self::pow10(self.num_digits().div_ceil(n as usize));
+
+        loop {
+            let y = (x.mul_small(n - 1) + self.clone() / x.clone().pow(n - 1)).div_rem_small(n).0;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+}
+
+impl ops::Div<BigInt> for BigInt {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("divisor can't be 0")
+    }
+}
+
+impl ops::Rem<BigInt> for BigInt {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.checked_rem(rhs).expect("divisor can't be 0")
+    }
+}
+
+impl ops::Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> Self::Output {
+        self.clone() + rhs.clone()
+    }
+}
+
+impl ops::Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> Self::Output {
+        self.clone() - rhs.clone()
+    }
+}
+
+impl ops::Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> Self::Output {
+        self.clone() * rhs.clone()
+    }
+}
+
+impl ops::Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: &BigInt) -> Self::Output {
+        self.clone() / rhs.clone()
+    }
+}
+
+impl ops::Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: &BigInt) -> Self::Output {
+        self.clone() % rhs.clone()
+    }
+}
+
+impl ops::Shl<u32> for BigInt {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        self.shl_bits(rhs)
+    }
+}
+
+impl ops::Shr<u32> for BigInt {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        self.shr_bits(rhs)
+    }
+}
+
+impl ops::BitAnd<BigInt> for BigInt {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.bitwise(&rhs, |a, b| a & b)
+    }
+}
+
+impl ops::BitOr<BigInt> for BigInt {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.bitwise(&rhs, |a, b| a | b)
+    }
+}
+
+impl ops::BitXor<BigInt> for BigInt {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.bitwise(&rhs, |a, b| a ^ b)
+    }
+}
+
+// 补码下 !x == -(x + 1)
+impl ops::Not for BigInt {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        let mut complement = -self;
+        complement.decrement();
+        complement
+    }
+}
+
+impl ops::AddAssign<BigInt> for BigInt {
+    fn add_assign(&mut self, rhs: Self) {
+        if self.positive != rhs.positive {
+            let lhs = std::mem::take(self);
+            *self = lhs + rhs;
+            return;
+        }
+
+        // 以下同号，原地逐 limb 相加
+        if self.value.len() < rhs.value.len() {
+            self.value.resize(rhs.value.len(), 0);
+        }
+
+        let mut carry = 0;
+        for i in 0..self.value.len() {
+            if i >= rhs.value.len() && carry == 0 { break; }
+            let s = self.value[i] as u64 + carry + rhs.value.get(i).copied().unwrap_or(0) as u64;
+            self.value[i] = (s % LIMB_BASE) as u32;
+            carry = s / LIMB_BASE;
+        }
+        if carry > 0 {
+            self.value.push(carry as u32);
+        }
+    }
+}
+
+impl ops::SubAssign<BigInt> for BigInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self += -rhs;
+    }
+}
+
+impl ops::MulAssign<BigInt> for BigInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::take(self);
+        *self = lhs * rhs;
+    }
+}
+
+impl ops::DivAssign<BigInt> for BigInt {
+    fn div_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::take(self);
+        *self = lhs / rhs;
+    }
+}
+
+impl ops::RemAssign<BigInt> for BigInt {
+    fn rem_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::take(self);
+        *self = lhs % rhs;
+    }
+}
+
+impl std::iter::Sum<BigInt> for BigInt {
+    fn sum<I: Iterator<Item=BigInt>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |mut acc, v| {
+            acc += v;
+            acc
+        })
+    }
+}
+
+impl<'a> std::iter::Sum<&'a BigInt> for BigInt {
+    fn sum<I: Iterator<Item=&'a BigInt>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl std::iter::Product<BigInt> for BigInt {
+    fn product<I: Iterator<Item=BigInt>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, v| acc * v)
+    }
+}
+
+impl<'a> std::iter::Product<&'a BigInt> for BigInt {
+    fn product<I: Iterator<Item=&'a BigInt>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
+// 十进制各位，高位在前
+impl std::iter::FromIterator<u8> for BigInt {
+    fn from_iter<I: IntoIterator<Item=u8>>(iter: I) -> Self {
+        let digits: Vec<i8> = iter.into_iter()
+            .map(|d| {
+                if d > 9 { panic!("decimal digit must be in 0..=9") }
+                d as i8
+            })
+            .collect();
+        let mut integer = Self {
+            positive: true,
+            value: Self::to_limbs(&digits),
+        };
+        // 空迭代器同样得到零
+        integer.trim_zero();
+        integer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::time::Instant;
+
+    use num_bigint::{RandBigInt, ToBigInt};
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_integer_format() {
+        assert!(catch_unwind(|| BigInt::new("a")).is_err());
+        assert!(catch_unwind(|| BigInt::new("1234+")).is_err());
+        assert!(catch_unwind(|| BigInt::new("--1234")).is_err());
+        assert!(catch_unwind(|| BigInt::new("++1234")).is_err());
+        assert!(catch_unwind(|| BigInt::new("+12.34")).is_err());
+
+        assert_eq!(format!("{}", BigInt::new("1234")), "1234".to_string());
+        assert_eq!(format!("{}", BigInt::new("+1234")), "1234".to_string());
+        assert_eq!(format!("{}", BigInt::new("-1234")), "-1234".to_string());
+        assert_eq!(format!("{}", BigInt::new("001234")), "1234".to_string());
+        assert_eq!(format!("{}", BigInt::new("+001234")), "1234".to_string());
+        assert_eq!(format!("{}", BigInt::new("-001234")), "-1234".to_string());
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(BigInt::try_new("").err(), Some(ParseBigIntError::Empty));
+        assert_eq!(BigInt::try_new("+").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("-").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("--1234").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("+-1234").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("1234+").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("12-34").err(), Some(ParseBigIntError::InvalidSign));
+        assert_eq!(BigInt::try_new("a").err(), Some(ParseBigIntError::InvalidDigit('a')));
+        assert_eq!(BigInt::try_new("+12.34").err(), Some(ParseBigIntError::InvalidDigit('.')));
+        assert_eq!(BigInt::try_new(" 1234").err(), Some(ParseBigIntError::InvalidDigit(' ')));
+        assert_eq!(BigInt::try_new("12٣4").err(), Some(ParseBigIntError::InvalidDigit('٣')));
+        assert_eq!(BigInt::try_new("１２").err(), Some(ParseBigIntError::InvalidDigit('１')));
+
+        assert_eq!(format!("{}", BigInt::try_new("-001234").unwrap()), "-1234".to_string());
+        assert_eq!(format!("{}", BigInt::try_new("-0").unwrap()), "0".to_string());
+        assert_eq!(format!("{}", BigInt::try_new("000").unwrap()), "0".to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let n: BigInt = "12345".parse().unwrap();
+        assert_eq!(format!("{}", n), "12345".to_string());
+
+        let lines = "1\n-20\n+300";
+        let parsed: Vec<BigInt> = lines.lines().map(str::parse).collect::<Result<_, _>>().unwrap();
+        assert_eq!(parsed.iter().map(|n| format!("{}", n)).collect::<Vec<_>>(), vec!["1", "-20", "300"]);
+
+        let invalid: Result<Vec<BigInt>, _> = "1\nx\n3".lines().map(str::parse).collect();
+        assert_eq!(invalid.err(), Some(ParseBigIntError::InvalidDigit('x')));
+
+        fn sum(a: &str, b: &str) -> Result<BigInt, ParseBigIntError> {
+            Ok(a.parse::<BigInt>()? + b.parse::<BigInt>()?)
+        }
+        assert_eq!(format!("{}", sum("40", "2").unwrap()), "42".to_string());
+        assert_eq!(sum("40", "").err(), Some(ParseBigIntError::Empty));
+        assert_eq!(sum("4-0", "2").err(), Some(ParseBigIntError::InvalidSign));
+    }
+
+    #[test]
+    fn test_from_str_trimmed() {
+        assert_eq!(BigInt::from_str_trimmed("  42  "), Ok(BigInt::new("42")));
+        assert_eq!(BigInt::from_str_trimmed("\t-7\n"), Ok(BigInt::new("-7")));
+        assert_eq!(BigInt::from_str_trimmed("\r\n+1_000 "), Ok(BigInt::new("1000")));
+        assert_eq!(BigInt::from_str_trimmed("0"), Ok(BigInt::new("0")));
+        assert_eq!(BigInt::from_str_trimmed("1 2"), Err(ParseBigIntError::InvalidDigit(' ')));
+        assert_eq!(BigInt::from_str_trimmed(" - 7"), Err(ParseBigIntError::InvalidDigit(' ')));
+        assert_eq!(BigInt::from_str_trimmed(" \t "), Err(ParseBigIntError::Empty));
+        assert_eq!(BigInt::from_str_trimmed("\u{a0}1"), Err(ParseBigIntError::InvalidDigit('\u{a0}')));
+        assert!(BigInt::try_new(" 42").is_err());
+    }
+
+    #[test]
+    fn test_from_scientific() {
+        let parse = |v: &str| BigInt::from_scientific(v).map(|i| format!("{}", i));
+        assert_eq!(parse("1.5e3"), Ok("1500".to_string()));
+        assert_eq!(parse("1.5E1"), Ok("15".to_string()));
+        assert_eq!(parse("-2.50e+2"), Ok("-250".to_string()));
+        assert_eq!(parse("+12e0"), Ok("12".to_string()));
+        assert_eq!(parse("12"), Ok("12".to_string()));
+        assert_eq!(parse("1200e-2"), Ok("12".to_string()));
+        assert_eq!(parse("-1.000e0"), Ok("-1".to_string()));
+        assert_eq!(parse(".5e1"), Ok("5".to_string()));
+        assert_eq!(parse("-0.0e-5"), Ok("0".to_string()));
+        assert_eq!(parse("0e-1000000000000"), Ok("0".to_string()));
+        assert_eq!(parse("0e1000000000000"), Ok("0".to_string()));
+        assert_eq!(parse("123456789.123456789e9"), Ok("123456789123456789".to_string()));
+
+        assert_eq!(parse("1.5e0"), Err(ParseBigIntError::NotAnInteger));
+        assert_eq!(parse("1.5"), Err(ParseBigIntError::NotAnInteger));
+        assert_eq!(parse("-15e-1"), Err(ParseBigIntError::NotAnInteger));
+        assert_eq!(parse("1e-1000000000000"), Err(ParseBigIntError::NotAnInteger));
+        assert_eq!(parse("1e99999999999999999999"), Err(ParseBigIntError::ExponentOutOfRange));
+        assert_eq!(parse("1e-99999999999999999999"), Err(ParseBigIntError::ExponentOutOfRange));
+        assert_eq!(parse("1e1000000000000"), Err(ParseBigIntError::ExponentOutOfRange));
+        assert_eq!(parse("1e9223372036854775807"), Err(ParseBigIntError::ExponentOutOfRange));
+        assert_eq!(parse("1e16777216"), Err(ParseBigIntError::ExponentOutOfRange));
+        assert_eq!(parse("1e16777215").map(|v| v.len()), Ok(16777216));
+        assert_eq!(parse("1e1_0"), Err(ParseBigIntError::InvalidDigit('_')));
+        assert_eq!(parse("1e+-3"), Err(ParseBigIntError::InvalidSign));
+        assert_eq!(parse("1e-"), Err(ParseBigIntError::InvalidSign));
+        assert_eq!(format!("{}", ParseBigIntError::ExponentOutOfRange), "exponent in string is out of range");
+        assert_eq!(parse(""), Err(ParseBigIntError::Empty));
+        assert_eq!(parse("1e"), Err(ParseBigIntError::Empty));
+        assert_eq!(parse("."), Err(ParseBigIntError::Empty));
+        assert_eq!(parse("-e3"), Err(ParseBigIntError::InvalidSign));
+        assert_eq!(parse("1.2.3e5"), Err(ParseBigIntError::InvalidDigit('.')));
+        assert_eq!(parse("1e2e3"), Err(ParseBigIntError::InvalidDigit('e')));
+        assert_eq!(parse("1_.5e1"), Err(ParseBigIntError::InvalidDigit('_')));
+        assert_eq!(format!("{}", ParseBigIntError::NotAnInteger), "number in string is not an integer");
+    }
+
+    #[test]
+    fn test_parse_long_string() {
+        let v = "9876543210".repeat(20000);
+        let integer = BigInt::new(format!("-000{}", v).as_str());
+        assert_eq!(format!("{}", integer), format!("-{}", v));
+        assert_eq!(format!("{}", integer), format!("{}", -v.parse::<num_bigint::BigInt>().unwrap()));
+    }
+
+    #[test]
+    fn test_comparator() {
+        assert!(BigInt::new("0") == BigInt::new("0"));
+        assert!(BigInt::new("0") != BigInt::new("1"));
+        assert!(BigInt::new("1") != -BigInt::new("1"));
+        assert!(BigInt::new("1") < BigInt::new("2"));
+        assert!(BigInt::new("1") <= BigInt::new("1"));
+        assert!(BigInt::new("2") > BigInt::new("1"));
+        assert!(BigInt::new("2") >= BigInt::new("2"));
+        assert!(BigInt::new("10") > BigInt::new("9"));
+        assert!(BigInt::new("-5") < BigInt::new("-3"));
+        assert!(BigInt::new("-10") < BigInt::new("-9"));
+        assert!(BigInt::new("-1") < BigInt::new("0"));
+        assert!(BigInt::new("-0") == BigInt::new("0"));
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut values: Vec<BigInt> = ["12", "-3", "0", "-0", "-100", "7", "+0", "-2", "1000", "-99"]
+            .iter()
+            .map(|v| BigInt::new(v))
+            .collect();
+        values.sort();
+        assert_eq!(values.iter().map(|v| format!("{}", v)).collect::<Vec<_>>(),
+                   vec!["-100", "-99", "-3", "-2", "0", "0", "0", "7", "12", "1000"]);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let a = rng.gen_bigint(100);
+            let b = rng.gen_bigint(100);
+            let tested_a = BigInt::new(format!("{}", a).as_str());
+            let tested_b = BigInt::new(format!("{}", b).as_str());
+            assert_eq!(tested_a.cmp(&tested_b), a.cmp(&b));
+        }
+
+        let mut set = std::collections::BTreeSet::new();
+        for v in ["5", "-5", "0", "-0", "5", "42"] {
+            set.insert(BigInt::new(v));
+        }
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&BigInt::new("-5")));
+        assert_eq!(set.iter().map(|v| format!("{}", v)).collect::<Vec<_>>(), vec!["-5", "0", "5", "42"]);
+    }
+
+    #[test]
+    fn test_ng_operator() {
+        assert!(BigInt::new("100") != -BigInt::new("100"));
+        assert_eq!(format!("{}", -BigInt::new("100")), "-100".to_string());
+        assert_eq!(format!("{}", -BigInt::new("0")), "0".to_string());
+        assert_eq!(format!("{}", -BigInt::new("-0")), "0".to_string());
+
+        let v = BigInt::new("-12345");
+        assert_eq!(format!("{}", -&v), "12345".to_string());
+        assert!(-&-&v == v);
+        let zero = BigInt::new("0");
+        assert!(-&zero == zero);
+        assert!(-&-&zero == zero);
+    }
+
+
+    fn test_operator(f: fn(BigInt, BigInt, num_bigint::BigInt, num_bigint::BigInt)
+                           -> (BigInt, num_bigint::BigInt)) {
+        test_operator_with(f, true);
+    }
+
+    fn test_div_like_operator(f: fn(BigInt, BigInt, num_bigint::BigInt, num_bigint::BigInt)
+                                    -> (BigInt, num_bigint::BigInt)) {
+        test_operator_with(f, false);
+    }
+
+    fn test_operator_with(f: fn(BigInt, BigInt, num_bigint::BigInt, num_bigint::BigInt)
+                                -> (BigInt, num_bigint::BigInt),
+                          allow_zero_rhs: bool) {
+        let mut rng = rand::thread_rng();
+        let low = -10000.to_bigint().unwrap();
+        let high = 10000.to_bigint().unwrap();
+        let zero = 0.to_bigint().unwrap();
+
+        for _ in 0..1000 {
+            let a = rng.gen_bigint(1000);
+            let mut b = rng.gen_bigint_range(&low, &high);
+            while !allow_zero_rhs && b == zero {
+                b = rng.gen_bigint_range(&low, &high);
+            }
+            let a_string = format!("{}", a);
+            let b_string = format!("{}", b);
+
+            let (ret, expected_ret) = f(
+                BigInt::new(a_string.as_str()),
+                BigInt::new(b_string.as_str()),
+                a,
+                b,
+            );
+            assert_eq!(format!("{}", ret), format!("{}", expected_ret));
+        }
+    }
+
+    #[test]
+    fn test_add_operator() {
+        test_operator(|tested_a, tested_b, a, b|
+            (tested_a + tested_b, a + b)
+        );
+    }
+
+    #[test]
+    fn test_sub_operator() {
+        test_operator(|tested_a, tested_b, a, b|
+            (tested_a - tested_b, a - b)
+        );
+    }
+
+    #[test]
+    fn test_mul_operator() {
+        test_operator(|tested_a, tested_b, a, b|
+            (tested_a * tested_b, a * b)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_zero() {
+        let _ = BigInt::new("100") / BigInt::new("0");
+    }
+
+    #[test]
+    fn test_div_operator() {
+        test_div_like_operator(|tested_a, tested_b, a, b|
+            (tested_a / tested_b, a / b)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor can't be 0")]
+    fn test_rem_zero() {
+        let _ = BigInt::new("100") % BigInt::new("0");
+    }
+
+    #[test]
+    fn test_rem_operator() {
+        assert_eq!(format!("{}", BigInt::new("17") % BigInt::new("5")), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("-17") % BigInt::new("5")), "-2".to_string());
+        assert_eq!(format!("{}", BigInt::new("17") % BigInt::new("-5")), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("-17") % BigInt::new("-5")), "-2".to_string());
+        assert_eq!(format!("{}", BigInt::new("15") % BigInt::new("5")), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("3") % BigInt::new("5")), "3".to_string());
+
+        test_div_like_operator(|tested_a, tested_b, a, b|
+            (tested_a % tested_b, a % b)
+        );
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let (q, r) = BigInt::new("-17").div_rem(BigInt::new("5"));
+        assert_eq!(format!("{}", q), "-3".to_string());
+        assert_eq!(format!("{}", r), "-2".to_string());
+
+        let mut rng = rand::thread_rng();
+        let zero = BigInt::new("0");
+        for _ in 0..200 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(500)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(200)).as_str());
+            if b == zero { continue; }
+
+            let (q, r) = a.clone().div_rem(b.clone());
+            assert!(q == a.clone() / b.clone());
+            assert!(r == a.clone() % b.clone());
+            assert!(q * b + r == a);
+        }
+    }
+
+    #[test]
+    fn test_is_divisible_by() {
+        let divisible = |a: &str, b: &str| BigInt::new(a).is_divisible_by(&BigInt::new(b));
+        assert!(divisible("1234", "2"));
+        assert!(!divisible("1235", "2"));
+        assert!(divisible("-1235", "5"));
+        assert!(!divisible("1234", "-5"));
+        assert!(divisible("123456789", "3"));
+        assert!(!divisible("123456788", "3"));
+        assert!(divisible("0", "-17"));
+        assert!(divisible("17", "1"));
+        assert!(!divisible("17", "170"));
+        assert!(divisible("-170", "170"));
+        assert!(!divisible("1235", "10"));
+        assert!(catch_unwind(|| BigInt::new("5").is_divisible_by(&BigInt::new("0"))).is_err());
+
+        let p = BigInt::new("170141183460469231731687303715884105727");
+        let q = BigInt::new("98765432109876543211");
+        assert!((p.clone() * q.clone()).is_divisible_by(&p));
+        assert!((p.clone() * q.clone()).is_divisible_by(&-q.clone()));
+        assert!(!(p.clone() * q.clone() + BigInt::new("1")).is_divisible_by(&p));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint_range(&-1000.to_bigint().unwrap(), &1000.to_bigint().unwrap())).as_str());
+            if b.is_zero() { continue; }
+            assert_eq!(a.is_divisible_by(&b), (a.clone() % b.clone()).is_zero());
+            let multiple = a * b.clone();
+            assert!(multiple.is_divisible_by(&b));
+        }
+    }
+
+    #[test]
+    fn test_div_rem_long_dividend() {
+        let mut rng = rand::thread_rng();
+        for (dividend_bits, divisor_bits) in [(20000u64, 64u64), (20000, 3000), (5000, 4990)] {
+            let a = rng.gen_bigint(dividend_bits);
+            let b = rng.gen_bigint(divisor_bits);
+            if b.bits() == 0 { continue; }
+
+            let (q, r) = BigInt::new(format!("{}", a).as_str()).div_rem(BigInt::new(format!("{}", b).as_str()));
+            assert_eq!(format!("{}", q), format!("{}", &a / &b));
+            assert_eq!(format!("{}", r), format!("{}", &a % &b));
+        }
+
+        // 商中含有大段连续的零
+        let a = BigInt::new("7").shift_decimal_left(3000) + BigInt::new("123456789");
+        let (q, r) = a.div_rem(BigInt::new("7000"));
+        assert_eq!(q, BigInt::new("1").shift_decimal_left(2997) + BigInt::new("17636"));
+        assert_eq!(r, BigInt::new("4789"));
+    }
+
+    #[test]
+    fn test_div_rem_limb_boundaries() {
+        // 由 0、1、10^9 / 2 附近与 999999999 等 limb 拼成的操作数，覆盖试商修正与加回除数的分支
+        let limbs = ["000000000", "000000001", "499999999", "500000000", "999999998", "999999999"];
+        let value = |rng: &mut rand::rngs::ThreadRng, max_limbs: usize| {
+            let lead = rng.gen_range(1..10).to_string();
+            let len = rng.gen_range(1..max_limbs);
+            let rest: String = (0..len).map(|_| limbs[rng.gen_range(0..limbs.len())]).collect();
+            lead + &rest
+        };
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..2000 {
+            let a = value(&mut rng, 8);
+            let b = value(&mut rng, 5);
+            let (expected_a, expected_b) = (a.parse::<num_bigint::BigInt>().unwrap(), b.parse::<num_bigint::BigInt>().unwrap());
+            let (q, r) = BigInt::new(&a).div_rem(BigInt::new(&b));
+            assert_eq!(format!("{}", q), format!("{}", &expected_a / &expected_b), "{} / {}", a, b);
+            assert_eq!(format!("{}", r), format!("{}", &expected_a % &expected_b), "{} % {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_from_primitive() {
+        assert_eq!(format!("{}", BigInt::from(0i64)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::from(0u64)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::from(0i32)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::from(0u32)), "0".to_string());
+
+        assert_eq!(format!("{}", BigInt::from(i64::MIN)), i64::MIN.to_string());
+        assert_eq!(format!("{}", BigInt::from(i64::MAX)), i64::MAX.to_string());
+        assert_eq!(format!("{}", BigInt::from(u64::MAX)), u64::MAX.to_string());
+        assert_eq!(format!("{}", BigInt::from(i32::MIN)), i32::MIN.to_string());
+        assert_eq!(format!("{}", BigInt::from(i32::MAX)), i32::MAX.to_string());
+        assert_eq!(format!("{}", BigInt::from(u32::MAX)), u32::MAX.to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let v: i64 = rng.gen();
+            assert_eq!(BigInt::from(v), BigInt::new(v.to_string().as_str()));
+            let v: u64 = rng.gen();
+            assert!(BigInt::from(v) == BigInt::new(v.to_string().as_str()));
+            let v: i32 = rng.gen();
+            assert!(BigInt::from(v) == BigInt::new(v.to_string().as_str()));
+        }
+    }
+
+    #[test]
+    fn test_from_wide_primitive() {
+        assert_eq!(format!("{}", BigInt::from(0i128)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::from(0u128)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::from(i128::MIN)), i128::MIN.to_string());
+        assert_eq!(format!("{}", BigInt::from(i128::MIN + 1)), (i128::MIN + 1).to_string());
+        assert_eq!(format!("{}", BigInt::from(i128::MAX)), i128::MAX.to_string());
+        assert_eq!(format!("{}", BigInt::from(u128::MAX)), u128::MAX.to_string());
+        assert_eq!(format!("{}", BigInt::from(u128::MAX - 1)), (u128::MAX - 1).to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let v: i128 = rng.gen();
+            assert_eq!(format!("{}", BigInt::from(v)), v.to_string());
+            let v: u128 = rng.gen();
+            assert_eq!(format!("{}", BigInt::from(v)), v.to_string());
+        }
+    }
+
+    #[test]
+    fn test_try_into_primitive() {
+        let max = BigInt::from(i64::MAX);
+        let min = BigInt::from(i64::MIN);
+        let one = BigInt::new("1");
+
+        assert_eq!(i64::try_from(BigInt::new("0")), Ok(0));
+        assert_eq!(i64::try_from(BigInt::new("-42")), Ok(-42));
+        assert_eq!(i64::try_from(max.clone()), Ok(i64::MAX));
+        assert_eq!(i64::try_from(min.clone()), Ok(i64::MIN));
+        assert_eq!(i64::try_from(max.clone() + one.clone()), Err(TryFromBigIntError::OutOfRange));
+        assert_eq!(i64::try_from(min - one.clone()), Err(TryFromBigIntError::OutOfRange));
+
+        assert_eq!(u64::try_from(BigInt::new("0")), Ok(0));
+        assert_eq!(u64::try_from(max + one.clone()), Ok(i64::MAX as u64 + 1));
+        assert_eq!(u64::try_from(BigInt::from(u64::MAX)), Ok(u64::MAX));
+        assert_eq!(u64::try_from(BigInt::from(u64::MAX) + one), Err(TryFromBigIntError::OutOfRange));
+        assert_eq!(u64::try_from(BigInt::new("-1")), Err(TryFromBigIntError::Negative));
+        assert_eq!(u64::try_from(BigInt::new("-99999999999999999999999")), Err(TryFromBigIntError::Negative));
+
+        let v: Result<i64, _> = BigInt::new("-123").try_into();
+        assert_eq!(v, Ok(-123));
+    }
+
+    #[test]
+    fn test_hash() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(BigInt::new("0"), "zero");
+        map.insert(BigInt::new("123"), "positive");
+        map.insert(BigInt::new("-123"), "negative");
+        map.insert(BigInt::new("98765432109876543210"), "large");
+
+        assert_eq!(map.get(&BigInt::new("-0")), Some(&"zero"));
+        assert_eq!(map.get(&BigInt::new("+000")), Some(&"zero"));
+        assert_eq!(map.get(&BigInt::new("0123")), Some(&"positive"));
+        assert_eq!(map.get(&-BigInt::new("123")), Some(&"negative"));
+        assert_eq!(map.get(&(BigInt::new("98765432109876543209") + BigInt::new("1"))), Some(&"large"));
+        assert_eq!(map.get(&BigInt::new("124")), None);
+
+        map.insert(BigInt::new("-0"), "still zero");
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&BigInt::new("0")), Some(&"still zero"));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut rng = rand::thread_rng();
+        let mut sum = BigInt::new("0");
+        let mut expected_sum = 0.to_bigint().unwrap();
+        let mut product = BigInt::new("1");
+        let mut expected_product = 1.to_bigint().unwrap();
+        for i in 0..500 {
+            let v = rng.gen_bigint(if i % 2 == 0 { 64 } else { 300 });
+            let tested_v = BigInt::new(format!("{}", v).as_str());
+            if i % 3 == 0 {
+                sum -= tested_v.clone();
+                expected_sum -= v.clone();
+            } else {
+                sum += tested_v.clone();
+                expected_sum += v.clone();
+            }
+            assert_eq!(format!("{}", sum), format!("{}", expected_sum));
+
+            if i < 20 {
+                product *= tested_v;
+                expected_product *= v;
+                assert_eq!(format!("{}", product), format!("{}", expected_product));
+            }
+        }
+
+        let mut v = BigInt::new("999");
+        v += BigInt::new("1");
+        assert_eq!(format!("{}", v), "1000".to_string());
+        v += BigInt::new("-1000");
+        assert_eq!(format!("{}", v), "0".to_string());
+        v -= BigInt::new("12");
+        assert_eq!(format!("{}", v), "-12".to_string());
+        v += BigInt::new("-9999");
+        assert_eq!(format!("{}", v), "-10011".to_string());
+        v /= BigInt::new("10");
+        assert_eq!(format!("{}", v), "-1001".to_string());
+        v %= BigInt::new("7");
+        assert_eq!(format!("{}", v), "0".to_string());
+
+        // 除数为零的 panic 被捕获后，左操作数仍是规范化的值
+        let mut v = BigInt::new("-42");
+        assert!(catch_unwind(AssertUnwindSafe(|| v /= BigInt::zero())).is_err());
+        assert_eq!(v, BigInt::zero());
+        assert_eq!(format!("{}", v), "0".to_string());
+        let mut v = BigInt::new("-42");
+        assert!(catch_unwind(AssertUnwindSafe(|| v %= BigInt::zero())).is_err());
+        assert_eq!(v, BigInt::zero());
+    }
+
+    #[test]
+    fn test_ref_operators() {
+        let mut rng = rand::thread_rng();
+        let zero = BigInt::new("0");
+        for _ in 0..200 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(100)).as_str());
+
+            assert!(&a + &b == a.clone() + b.clone());
+            assert!(&a - &b == a.clone() - b.clone());
+            assert!(&a * &b == a.clone() * b.clone());
+            if b != zero {
+                assert!(&a / &b == a.clone() / b.clone());
+                assert!(&a % &b == a.clone() % b.clone());
+            }
+        }
+
+        let values = [BigInt::new("1"), BigInt::new("-20"), BigInt::new("300")];
+        let sum = values.iter().fold(BigInt::new("0"), |acc, v| &acc + v);
+        assert_eq!(format!("{}", sum), "281".to_string());
+        assert_eq!(values.len(), 3);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let values: Vec<BigInt> = (-20..=30).filter(|&i| i != 0).map(BigInt::from).collect();
+
+        let expected_sum = values.iter().fold(BigInt::new("0"), |acc, v| acc + v.clone());
+        let expected_product = values.iter().fold(BigInt::new("1"), |acc, v| acc * v.clone());
+        assert!(values.iter().sum::<BigInt>() == expected_sum);
+        assert!(values.iter().cloned().sum::<BigInt>() == expected_sum);
+        assert!(values.iter().product::<BigInt>() == expected_product);
+        assert!(values.iter().cloned().product::<BigInt>() == expected_product);
+        assert_eq!(format!("{}", expected_sum), "255".to_string());
+
+        let empty: Vec<BigInt> = vec![];
+        assert_eq!(format!("{}", empty.iter().sum::<BigInt>()), "0".to_string());
+        assert_eq!(format!("{}", empty.into_iter().product::<BigInt>()), "1".to_string());
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(format!("{}", BigInt::new("0").pow(0)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("0").pow(5)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("-3").pow(0)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("-3").pow(3)), "-27".to_string());
+        assert_eq!(format!("{}", BigInt::new("-3").pow(4)), "81".to_string());
+        assert_eq!(format!("{}", BigInt::new("2").pow(1000)),
+                   format!("{}", 2.to_bigint().unwrap().pow(1000)));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let a = rng.gen_bigint(64);
+            let exp = rng.gen_range(0..20);
+            assert_eq!(format!("{}", BigInt::new(format!("{}", a).as_str()).pow(exp)),
+                       format!("{}", a.pow(exp)));
+        }
+    }
+
+    #[test]
+    fn test_checked_pow() {
+        assert_eq!(BigInt::new("10").checked_pow(3, 4), Some(BigInt::new("1000")));
+        assert_eq!(BigInt::new("10").checked_pow(4, 4), None);
+        assert_eq!(BigInt::new("9").checked_pow(4, 4), Some(BigInt::new("6561")));
+        assert_eq!(BigInt::new("-2").checked_pow(13, 4), Some(BigInt::new("-8192")));
+        assert_eq!(BigInt::new("2").checked_pow(14, 4), None);
+        assert_eq!(BigInt::new("0").checked_pow(u32::MAX, 1), Some(BigInt::new("0")));
+        assert_eq!(BigInt::new("-1").checked_pow(u32::MAX, 1), Some(BigInt::new("-1")));
+        assert_eq!(BigInt::new("12345").checked_pow(0, 1), Some(BigInt::new("1")));
+        assert_eq!(BigInt::new("12345").checked_pow(0, 0), None);
+        assert_eq!(BigInt::new("2").checked_pow(u32::MAX, 1000), None);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(200)).as_str());
+            let exp = rng.gen_range(0..20);
+            let digits = a.clone().pow(exp).num_digits();
+            assert_eq!(a.clone().checked_pow(exp, digits), Some(a.clone().pow(exp)));
+            assert_eq!(a.clone().checked_pow(exp, digits + 1), Some(a.clone().pow(exp)));
+            assert_eq!(a.checked_pow(exp, digits - 1), None);
+        }
+    }
+
+    #[test]
+    fn test_pow_big() {
+        assert_eq!(format!("{}", BigInt::new("0").pow_big(&BigInt::new("0"))), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("0").pow_big(&BigInt::new("5"))), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("-3").pow_big(&BigInt::new("3"))), "-27".to_string());
+        assert_eq!(format!("{}", BigInt::new("-1").pow_big(&BigInt::new("123456789012345678901"))), "-1".to_string());
+        assert_eq!(format!("{}", BigInt::new("1").pow_big(&BigInt::new("98765432109876543210"))), "1".to_string());
+        assert!(catch_unwind(|| BigInt::new("2").pow_big(&BigInt::new("-1"))).is_err());
+
+        let mut rng = rand::thread_rng();
+        for exp in (0..20).chain([255, 256, 257, 1000]) {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(64)).as_str());
+            assert_eq!(a.clone().pow_big(&BigInt::from(exp)), a.pow(exp));
+        }
+    }
+
+    #[test]
+    fn test_modpow() {
+        let m = BigInt::new("13");
+        assert_eq!(format!("{}", BigInt::new("4").modpow(&BigInt::new("0"), &m)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("4").modpow(&BigInt::new("0"), &BigInt::new("1"))), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("4").modpow(&BigInt::new("13"), &BigInt::new("497"))), "445".to_string());
+        assert_eq!(format!("{}", BigInt::new("-4").modpow(&BigInt::new("3"), &m)), "1".to_string());
+        assert!(catch_unwind(|| BigInt::new("4").modpow(&BigInt::new("2"), &BigInt::new("0"))).is_err());
+        assert!(catch_unwind(|| BigInt::new("4").modpow(&BigInt::new("2"), &BigInt::new("-7"))).is_err());
+
+        let mut rng = rand::thread_rng();
+        let one = 1.to_bigint().unwrap();
+        for _ in 0..50 {
+            let base = rng.gen_bigint(200);
+            let exp = rng.gen_biguint(64).to_bigint().unwrap();
+            let modulus = rng.gen_biguint(100).to_bigint().unwrap() + &one;
+            let tested = BigInt::new(format!("{}", base).as_str()).modpow(
+                &BigInt::new(format!("{}", exp).as_str()),
+                &BigInt::new(format!("{}", modulus).as_str()),
+            );
+            assert_eq!(format!("{}", tested), format!("{}", base.modpow(&exp, &modulus)));
+        }
+    }
+
+    #[test]
+    fn test_gcd_binary() {
+        let gcd_binary = |a: &str, b: &str| format!("{}", BigInt::new(a).gcd_binary(BigInt::new(b)));
+        assert_eq!(gcd_binary("0", "0"), "0".to_string());
+        assert_eq!(gcd_binary("0", "-7"), "7".to_string());
+        assert_eq!(gcd_binary("-12", "0"), "12".to_string());
+        assert_eq!(gcd_binary("48", "-18"), "6".to_string());
+        assert_eq!(gcd_binary("1024", "96"), "32".to_string());
+
+        let mut rng = rand::thread_rng();
+        for bits in [8u64, 64, 256, 1024] {
+            for _ in 0..20 {
+                let a = BigInt::new(format!("{}", rng.gen_bigint(bits)).as_str());
+                let b = BigInt::new(format!("{}", rng.gen_bigint(bits)).as_str());
+                let common = BigInt::new("2").pow(rng.gen_range(0..100)) * BigInt::from(rng.gen_range(1..1000));
+                let (a, b) = (a * common.clone(), b * common);
+                assert_eq!(a.clone().gcd_binary(b.clone()), a.clone().gcd(b.clone()));
+
+                // 绕过分派，直接与欧几里得算法比较
+                let (mut x, mut y) = (a.abs(), b.abs());
+                while !y.is_zero() {
+                    let r = x % y.clone();
+                    x = y;
+                    y = r;
+                }
+                assert_eq!(a.gcd_binary(b), x);
+            }
+        }
+    }
+
+    // cargo test --release -- --ignored --nocapture 查看耗时
+    #[test]
+    #[ignore]
+    fn bench_gcd() {
+        let mut rng = rand::thread_rng();
+        for digits in [16u64, 64, 256, 1024] {
+            let bits = digits * 3322 / 1000;
+            let common = BigInt::new(format!("{}", rng.gen_biguint(bits / 2)).as_str());
+            let a = BigInt::new(format!("{}", rng.gen_biguint(bits / 2)).as_str()) * common.clone();
+            let b = BigInt::new(format!("{}", rng.gen_biguint(bits / 2)).as_str()) * common;
+
+            let start = Instant::now();
+            let (mut x, mut y) = (a.clone(), b.clone());
+            while !y.is_zero() {
+                let r = x % y.clone();
+                x = y;
+                y = r;
+            }
+            let euclid_elapsed = start.elapsed();
+            let start = Instant::now();
+            let binary = a.clone().gcd_binary(b.clone());
+            let binary_elapsed = start.elapsed();
+            assert_eq!(binary, x);
+            println!("gcd of {}-digit values: euclid {:?}, binary {:?}", digits, euclid_elapsed, binary_elapsed);
+        }
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let gcd = |a: &str, b: &str| format!("{}", BigInt::new(a).gcd(BigInt::new(b)));
+        let lcm = |a: &str, b: &str| format!("{}", BigInt::new(a).lcm(BigInt::new(b)));
+
+        assert_eq!(gcd("0", "0"), "0".to_string());
+        assert_eq!(gcd("0", "-7"), "7".to_string());
+        assert_eq!(gcd("48", "18"), "6".to_string());
+        assert_eq!(gcd("-48", "18"), "6".to_string());
+        assert_eq!(gcd("-48", "-18"), "6".to_string());
+        assert_eq!(gcd("17", "5"), "1".to_string());
+        assert_eq!(gcd("123456789012345678901234567890", "987654321098765432109876543210"),
+                   "9000000000900000000090".to_string());
+
+        assert_eq!(lcm("0", "0"), "0".to_string());
+        assert_eq!(lcm("0", "5"), "0".to_string());
+        assert_eq!(lcm("4", "6"), "12".to_string());
+        assert_eq!(lcm("-4", "6"), "12".to_string());
+        assert_eq!(lcm("-4", "-6"), "12".to_string());
+        assert_eq!(lcm("21", "6"), "42".to_string());
+
+        let mut rng = rand::thread_rng();
+        let zero = BigInt::new("0");
+        for _ in 0..50 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(128)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(128)).as_str());
+            if a == zero || b == zero { continue; }
+
+            let gcd = a.clone().gcd(b.clone());
+            let lcm = a.clone().lcm(b.clone());
+            assert!(a.clone() % gcd.clone() == zero);
+            assert!(b.clone() % gcd.clone() == zero);
+            assert!(gcd * lcm == (a * b).abs());
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(format!("{}", BigInt::new("0").sqrt()), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("1").sqrt()), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("3").sqrt()), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("4").sqrt()), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("99").sqrt()), "9".to_string());
+        assert_eq!(format!("{}", BigInt::new("100").sqrt()), "10".to_string());
+        assert!(catch_unwind(|| BigInt::new("-4").sqrt()).is_err());
+
+        let root = BigInt::new("123456789012345678901234567890123456789012345678901234567890");
+        assert!((root.clone() * root.clone()).sqrt() == root);
+
+        let mut rng = rand::thread_rng();
+        let one = BigInt::new("1");
+        for _ in 0..20 {
+            let n = BigInt::new(format!("{}", rng.gen_biguint(400)).as_str());
+            let r = n.sqrt();
+            let next = r.clone() + one.clone();
+            assert!(r.clone() * r <= n);
+            assert!(n < next.clone() * next);
+        }
+    }
+
+    #[test]
+    fn test_nth_root() {
+        assert_eq!(format!("{}", BigInt::new("0").nth_root(3)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("12345").nth_root(1)), "12345".to_string());
+        assert_eq!(format!("{}", BigInt::new("26").nth_root(3)), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("27").nth_root(3)), "3".to_string());
+        assert_eq!(format!("{}", BigInt::new("1023").nth_root(10)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("1024").nth_root(10)), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("-27").nth_root(3)), "-3".to_string());
+        assert_eq!(format!("{}", BigInt::new("-30").nth_root(3)), "-3".to_string());
+        assert_eq!(format!("{}", BigInt::new("-5").nth_root(1)), "-5".to_string());
+        assert!(catch_unwind(|| BigInt::new("8").nth_root(0)).is_err());
+        assert!(catch_unwind(|| BigInt::new("-16").nth_root(4)).is_err());
+
+        let root = BigInt::new("123456789012345678901234567890");
+        assert!(root.clone().pow(5).nth_root(5) == root);
+
+        let mut rng = rand::thread_rng();
+        let one = BigInt::new("1");
+        for _ in 0..20 {
+            let v = BigInt::new(format!("{}", rng.gen_biguint(300)).as_str());
+            let n = rng.gen_range(2..8);
+            let r = v.nth_root(n);
+            let next = r.clone() + one.clone();
+            assert!(r.pow(n) <= v);
+            assert!(v < next.pow(n));
+        }
+    }
+
+    #[test]
+    fn test_abs() {
+        let v = -BigInt::new("123");
+        assert!(v.abs() == BigInt::new("123"));
+        assert_eq!(format!("{}", v), "-123".to_string());
+        assert!(BigInt::new("123").abs() == BigInt::new("123"));
+
+        let zero = BigInt::new("0").abs();
+        assert!(zero == BigInt::new("0"));
+        assert!(zero >= BigInt::new("0"));
+        assert!(BigInt::new("-0").abs() == BigInt::new("0"));
+    }
+
+    #[test]
+    fn test_signum() {
+        assert_eq!(BigInt::new("0").signum(), 0);
+        assert_eq!(BigInt::new("-0").signum(), 0);
+        assert_eq!(BigInt::new("+000").signum(), 0);
+        assert_eq!(BigInt::new("42").signum(), 1);
+        assert_eq!(BigInt::new("-42").signum(), -1);
+        assert_eq!(BigInt::new("-98765432109876543210").signum(), -1);
+        assert_eq!((BigInt::new("5") - BigInt::new("5")).signum(), 0);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let zero = BigInt::new("-0");
+        assert!(zero.is_zero() && !zero.is_positive() && !zero.is_negative());
+        assert!(zero.is_even() && !zero.is_odd());
+
+        let v = BigInt::new("7");
+        assert!(!v.is_zero() && v.is_positive() && !v.is_negative());
+        assert!(!v.is_even() && v.is_odd());
+
+        let v = BigInt::new("-8");
+        assert!(!v.is_zero() && !v.is_positive() && v.is_negative());
+        assert!(v.is_even() && !v.is_odd());
+
+        let v = BigInt::new("-12345678901234567890123456789");
+        assert!(v.is_negative() && v.is_odd());
+        let v = BigInt::new("12345678901234567890123456780");
+        assert!(v.is_positive() && v.is_even());
+    }
+
+    #[test]
+    fn test_hex_format() {
+        assert_eq!(format!("{:x}", BigInt::new("0")), "0".to_string());
+        assert_eq!(format!("{:x}", BigInt::new("255")), "ff".to_string());
+        assert_eq!(format!("{:X}", BigInt::new("255")), "FF".to_string());
+        assert_eq!(format!("{:x}", BigInt::new("256")), "100".to_string());
+        assert_eq!(format!("{:x}", BigInt::new("-255")), "-ff".to_string());
+        assert_eq!(format!("{:#x}", BigInt::new("255")), "0xff".to_string());
+        assert_eq!(format!("{:x}", BigInt::from(u128::MAX)), format!("{:x}", u128::MAX));
+
+        let large = BigInt::from(u128::MAX) * BigInt::new("4096") + BigInt::new("171");
+        assert_eq!(format!("{:x}", large), "ffffffffffffffffffffffffffffffff0ab".to_string());
+        assert_eq!(format!("{:X}", -large), "-FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF0AB".to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let v = rng.gen_bigint(300);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            assert_eq!(format!("{:x}", tested), format!("{:x}", v));
+            assert_eq!(format!("{:X}", tested), format!("{:X}", v));
+        }
+    }
+
+    #[test]
+    fn test_binary_octal_format() {
+        assert_eq!(format!("{:b}", BigInt::new("10")), "1010".to_string());
+        assert_eq!(format!("{:o}", BigInt::new("10")), "12".to_string());
+        assert_eq!(format!("{:b}", BigInt::new("0")), "0".to_string());
+        assert_eq!(format!("{:o}", BigInt::new("0")), "0".to_string());
+        assert_eq!(format!("{:b}", BigInt::new("-10")), "-1010".to_string());
+        assert_eq!(format!("{:o}", BigInt::new("-10")), "-12".to_string());
+        assert_eq!(format!("{:#b}", BigInt::new("5")), "0b101".to_string());
+        assert_eq!(format!("{:#o}", BigInt::new("8")), "0o10".to_string());
+
+        let mut rng = rand::thread_rng();
+        for v in [1u64, 7, 8, 255, 256, u32::MAX as u64, u64::MAX].into_iter().chain((0..20).map(|_| rng.gen())) {
+            assert_eq!(format!("{:b}", BigInt::from(v)), format!("{:b}", v));
+            assert_eq!(format!("{:o}", BigInt::from(v)), format!("{:o}", v));
+        }
+
+        let large = BigInt::from(u64::MAX).pow(3);
+        let expected = 1.to_bigint().unwrap() * u64::MAX * u64::MAX * u64::MAX;
+        assert_eq!(format!("{:b}", large), format!("{:b}", expected));
+        assert_eq!(format!("{:o}", large), format!("{:o}", expected));
+    }
+
+    #[test]
+    fn test_to_str_radix() {
+        assert_eq!(BigInt::new("0").to_str_radix(36), "0".to_string());
+        assert_eq!(BigInt::new("35").to_str_radix(36), "z".to_string());
+        assert_eq!(BigInt::new("-36").to_str_radix(36), "-10".to_string());
+        assert_eq!(BigInt::new("255").to_str_radix(2), "11111111".to_string());
+        assert_eq!(BigInt::new("1234").to_str_radix(10), "1234".to_string());
+        assert!(catch_unwind(|| BigInt::new("1").to_str_radix(1)).is_err());
+        assert!(catch_unwind(|| BigInt::new("1").to_str_radix(37)).is_err());
+
+        let mut rng = rand::thread_rng();
+        for radix in 2..=36 {
+            let v = rng.gen_bigint(200);
+            assert_eq!(BigInt::new(format!("{}", v).as_str()).to_str_radix(radix), v.to_str_radix(radix));
+        }
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        let parse = |s: &str, radix: u32| BigInt::from_str_radix(s, radix).map(|v| format!("{}", v));
+
+        assert_eq!(parse("ff", 16), Ok("255".to_string()));
+        assert_eq!(parse("FF", 16), Ok("255".to_string()));
+        assert_eq!(parse("-fF", 16), Ok("-255".to_string()));
+        assert_eq!(parse("+1010", 2), Ok("10".to_string()));
+        assert_eq!(parse("-0", 2), Ok("0".to_string()));
+        assert_eq!(parse("zz", 36), Ok("1295".to_string()));
+        assert_eq!(parse("0012", 8), Ok("10".to_string()));
+        assert_eq!(parse("-0012", 10), Ok("-12".to_string()));
+
+        assert_eq!(parse("", 16), Err(ParseBigIntError::Empty));
+        assert_eq!(parse("-", 16), Err(ParseBigIntError::InvalidSign));
+        assert_eq!(parse("1-0", 2), Err(ParseBigIntError::InvalidSign));
+        assert_eq!(parse("102", 2), Err(ParseBigIntError::InvalidDigit('2')));
+        assert_eq!(parse("fg", 16), Err(ParseBigIntError::InvalidDigit('g')));
+        assert!(catch_unwind(|| BigInt::from_str_radix("1", 37)).is_err());
+
+        let mut rng = rand::thread_rng();
+        for radix in 2..=36 {
+            let v = BigInt::new(format!("{}", rng.gen_bigint(200)).as_str());
+            let s = v.to_str_radix(radix);
+            assert!(BigInt::from_str_radix(s.as_str(), radix).unwrap() == v);
+            assert!(BigInt::from_str_radix(s.to_uppercase().as_str(), radix).unwrap() == v);
+        }
+    }
+
+    #[test]
+    fn test_radix_roundtrip() {
+        let mut rng = rand::thread_rng();
+        for radix in 2..=36u32 {
+            let base = BigInt::from(radix);
+            let mut values = vec![BigInt::new("0"), BigInt::new("1"), BigInt::new("-1"), base.clone(), -base.clone()];
+            for exp in [1u32, 2, 17] {
+                let power = base.clone().pow(exp);
+                values.push(power.clone() - BigInt::new("1"));
+                values.push(power.clone() + BigInt::new("1"));
+                values.push(-power);
+            }
+            for bits in [1u64, 8, 64, 300] {
+                values.extend((0..5).map(|_| BigInt::new(format!("{}", rng.gen_bigint(bits)).as_str())));
+            }
+
+            for x in values {
+                let s = x.to_str_radix(radix);
+                assert_eq!(BigInt::from_str_radix(s.as_str(), radix), Ok(x.clone()), "{} in radix {}", x, radix);
+                assert!(!s.starts_with('0') || s == "0", "{} in radix {}", s, radix);
+            }
+        }
+    }
+
+    #[test]
+    fn test_underscore_separators() {
+        assert!(BigInt::new("1_000_000") == BigInt::new("1000000"));
+        assert!(BigInt::new("-12_345_678_901_234_567_890") == BigInt::new("-12345678901234567890"));
+        assert!(BigInt::new("+0_0_1") == BigInt::new("1"));
+        assert!("1_2_3".parse::<BigInt>().unwrap() == BigInt::new("123"));
+        assert!(BigInt::from_str_radix("ff_ff", 16).unwrap() == BigInt::new("65535"));
+        assert!(BigInt::from_str_radix("-1010_1010", 2).unwrap() == BigInt::new("-170"));
+
+        for v in ["_1", "1_", "1__2", "-_1", "+_1", "_", "-_", "1_.2"] {
+            assert_eq!(BigInt::try_new(v).err(), Some(ParseBigIntError::InvalidDigit('_')), "{}", v);
+        }
+        assert_eq!(BigInt::from_str_radix("f__f", 16).err(), Some(ParseBigIntError::InvalidDigit('_')));
+        assert_eq!(BigInt::from_str_radix("1_2", 2).err(), Some(ParseBigIntError::InvalidDigit('_')));
+    }
+
+    // 逐个十进制位相乘的竖式乘法，作为按 limb 相乘的对照
+    fn schoolbook_mul(a: &BigInt, b: &BigInt) -> BigInt {
+        let x: Vec<u32> = a.digits().map(u32::from).collect();
+        let y: Vec<u32> = b.digits().map(u32::from).collect();
+        let mut product = vec![0; x.len() + y.len()];
+        for i in (0..x.len()).rev() {
+            for j in (0..y.len()).rev() {
+                let h = i + j;
+                let l = h + 1;
+                let p = x[i] * y[j] + product[l];
+                product[l] = p % 10;
+                product[h] += p / 10;
+            }
+        }
+        let product: Vec<u8> = product.into_iter().map(|d| d as u8).collect();
+        BigInt::from_digits(a.positive == b.positive, &product)
+    }
+
+    #[test]
+    fn test_mul_limbs() {
+        assert_eq!(format!("{}", BigInt::new("999999999") * BigInt::new("999999999")),
+                   "999999998000000001".to_string());
+        assert_eq!(format!("{}", BigInt::new("1000000000") * BigInt::new("-1000000000")),
+                   "-1000000000000000000".to_string());
+        assert_eq!(format!("{}", BigInt::new("-123456789123456789") * BigInt::new("-987654321")),
+                   "121932631234567900112635269".to_string());
+
+        let mut rng = rand::thread_rng();
+        let a = BigInt::new(format!("{}", rng.gen_biguint(3322 * 2)).as_str());
+        let b = BigInt::new(format!("{}", rng.gen_biguint(3322 * 2)).as_str());
+
+        assert!(a.clone() * b.clone() == schoolbook_mul(&a, &b));
+
+        for _ in 0..20 {
+            let x = rng.gen_bigint(3322);
+            let y = rng.gen_bigint(3322);
+            let product = BigInt::new(format!("{}", x).as_str()) * BigInt::new(format!("{}", y).as_str());
+            assert_eq!(format!("{}", product), format!("{}", x * y));
+        }
+    }
+
+    // cargo test --release -- --ignored --nocapture 查看耗时
+    #[test]
+    #[ignore]
+    fn bench_mul_limbs() {
+        let mut rng = rand::thread_rng();
+        for digits in [1000u64, 2000, 5000] {
+            let bits = digits * 3322 / 1000;
+            let a = BigInt::new(format!("{}", rng.gen_biguint(bits)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_biguint(bits)).as_str());
+
+            let start = Instant::now();
+            let expected = schoolbook_mul(&a, &b);
+            let schoolbook_elapsed = start.elapsed();
+            let start = Instant::now();
+            let product = a * b;
+            let limb_elapsed = start.elapsed();
+            assert_eq!(product, expected);
+            println!("{}-digit product: limbs {:?}, digit by digit {:?}", digits, limb_elapsed, schoolbook_elapsed);
+        }
+    }
+
+    #[test]
+    fn test_checked_div_rem() {
+        assert!(BigInt::new("100").checked_div(BigInt::new("0")).is_none());
+        assert!(BigInt::new("100").checked_rem(BigInt::new("-0")).is_none());
+        assert!(BigInt::new("0").checked_div(BigInt::new("0")).is_none());
+
+        let q = BigInt::new("-17").checked_div(BigInt::new("5")).unwrap();
+        let r = BigInt::new("-17").checked_rem(BigInt::new("5")).unwrap();
+        assert_eq!(format!("{}", q), "-3".to_string());
+        assert_eq!(format!("{}", r), "-2".to_string());
+        let q = BigInt::new("100000000000000000000").checked_div(BigInt::new("7")).unwrap();
+        let r = BigInt::new("100000000000000000000").checked_rem(BigInt::new("7")).unwrap();
+        assert_eq!(format!("{}", q), "14285714285714285714".to_string());
+        assert_eq!(format!("{}", r), "2".to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        for v in ["0", "-0", "42", "-42", "123456789012345678901234567890", "-98765432109876543210"] {
+            let integer = BigInt::new(v);
+            let json = serde_json::to_string(&integer).unwrap();
+            assert_eq!(json, format!("\"{}\"", integer));
+            assert!(serde_json::from_str::<BigInt>(json.as_str()).unwrap() == integer);
+        }
+
+        let values: Vec<BigInt> = serde_json::from_str(r#"["1", "-2", "+0003"]"#).unwrap();
+        assert_eq!(values.iter().map(|v| format!("{}", v)).collect::<Vec<_>>(), vec!["1", "-2", "3"]);
+
+        let err = serde_json::from_str::<BigInt>(r#""12a""#).err().unwrap();
+        assert!(err.to_string().contains("invalid digit found in string: 'a'"));
+        assert!(serde_json::from_str::<BigInt>(r#""""#).is_err());
+        assert!(serde_json::from_str::<BigInt>("12").is_err());
+    }
+
+    #[test]
+    fn test_bytes_be() {
+        assert_eq!(BigInt::new("0").to_bytes_be(), (true, vec![0]));
+        assert_eq!(BigInt::new("255").to_bytes_be(), (true, vec![255]));
+        assert_eq!(BigInt::new("-256").to_bytes_be(), (false, vec![1, 0]));
+        assert!(BigInt::from_bytes_be(true, &[]) == BigInt::new("0"));
+        assert!(BigInt::from_bytes_be(false, &[0, 0]) == BigInt::new("0"));
+        assert!(BigInt::from_bytes_be(false, &[0, 1, 0]) == BigInt::new("-256"));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(300);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            let (positive, bytes) = tested.to_bytes_be();
+            let (sign, expected_bytes) = v.to_bytes_be();
+            assert_eq!(bytes, expected_bytes);
+            assert_eq!(positive, sign != num_bigint::Sign::Minus);
+            assert!(BigInt::from_bytes_be(positive, bytes.as_slice()) == tested);
+        }
+    }
+
+    #[test]
+    fn test_bytes_le() {
+        assert_eq!(BigInt::new("0").to_bytes_le(), (true, vec![0]));
+        assert_eq!(BigInt::new("-0").to_bytes_le(), (true, vec![0]));
+        assert_eq!(BigInt::new("-258").to_bytes_le(), (false, vec![2, 1]));
+        assert!(BigInt::from_bytes_le(true, &[]) == BigInt::new("0"));
+        assert!(BigInt::from_bytes_le(true, &[0]) == BigInt::new("0"));
+        assert!(BigInt::from_bytes_le(true, &[1, 0, 0]) == BigInt::new("1"));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(300);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            let (positive, bytes) = tested.to_bytes_le();
+            let (sign, expected_bytes) = v.to_bytes_le();
+            assert_eq!(bytes, expected_bytes);
+            assert_eq!(positive, sign != num_bigint::Sign::Minus);
+            assert!(BigInt::from_bytes_le(positive, bytes.as_slice()) == tested);
+        }
+    }
+
+    #[test]
+    fn test_shift_operators() {
+        assert!(BigInt::new("1") << 100 == BigInt::new("2").pow(100));
+        assert_eq!(format!("{}", BigInt::new("-3") << 4), "-48".to_string());
+        assert_eq!(format!("{}", BigInt::new("0") << 10), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("48") >> 4), "3".to_string());
+        assert_eq!(format!("{}", BigInt::new("5") >> 1), "2".to_string());
+        assert_eq!(format!("{}", BigInt::new("5") >> 10), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("-5") >> 1), "-3".to_string());
+        assert_eq!(format!("{}", BigInt::new("-4") >> 1), "-2".to_string());
+        assert_eq!(format!("{}", BigInt::new("-5") >> 10), "-1".to_string());
+        assert_eq!(format!("{}", BigInt::new("7") >> 0), "7".to_string());
+
+        for v in [-1000i64, -129, -7, -1, 0, 1, 7, 129, 1000, i64::MIN, i64::MAX] {
+            for n in [0u32, 1, 3, 8, 63] {
+                assert_eq!(format!("{}", BigInt::from(v) >> n), (v >> n).to_string());
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let v = rng.gen_bigint(300);
+            let n = rng.gen_range(0..200);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            assert_eq!(format!("{}", tested.clone() << n), format!("{}", &v << n));
+            assert_eq!(format!("{}", tested >> n), format!("{}", &v >> n));
+        }
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        assert_eq!(format!("{}", BigInt::new("12") & BigInt::new("10")), "8".to_string());
+        assert_eq!(format!("{}", BigInt::new("12") | BigInt::new("10")), "14".to_string());
+        assert_eq!(format!("{}", BigInt::new("12") ^ BigInt::new("10")), "6".to_string());
+        assert_eq!(format!("{}", BigInt::new("-1") & BigInt::new("255")), "255".to_string());
+        assert_eq!(format!("{}", BigInt::new("-256") | BigInt::new("255")), "-1".to_string());
+        assert_eq!(format!("{}", BigInt::new("-128") ^ BigInt::new("127")), "-1".to_string());
+        assert_eq!(format!("{}", BigInt::new("0") ^ BigInt::new("0")), "0".to_string());
+
+        for a in [-300i64, -256, -129, -128, -1, 0, 1, 127, 128, 300, i64::MIN, i64::MAX] {
+            for b in [-300i64, -255, -1, 0, 1, 255, 65536, i64::MIN, i64::MAX] {
+                assert_eq!(format!("{}", BigInt::from(a) & BigInt::from(b)), (a & b).to_string());
+                assert_eq!(format!("{}", BigInt::from(a) | BigInt::from(b)), (a | b).to_string());
+                assert_eq!(format!("{}", BigInt::from(a) ^ BigInt::from(b)), (a ^ b).to_string());
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let (a_bits, b_bits) = (rng.gen_range(1..300), rng.gen_range(1..300));
+            let a = rng.gen_bigint(a_bits);
+            let b = rng.gen_bigint(b_bits);
+            let tested_a = BigInt::new(format!("{}", a).as_str());
+            let tested_b = BigInt::new(format!("{}", b).as_str());
+            assert_eq!(format!("{}", tested_a.clone() & tested_b.clone()), format!("{}", &a & &b));
+            assert_eq!(format!("{}", tested_a.clone() | tested_b.clone()), format!("{}", &a | &b));
+            assert_eq!(format!("{}", tested_a ^ tested_b), format!("{}", &a ^ &b));
+        }
+    }
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(BigInt::new("0").bits(), 0);
+        assert_eq!(BigInt::new("1").bits(), 1);
+        assert_eq!(BigInt::new("255").bits(), 8);
+        assert_eq!(BigInt::new("256").bits(), 9);
+        assert_eq!(BigInt::new("-256").bits(), 9);
+        for n in [1u32, 7, 8, 9, 63, 64, 65, 127, 128, 1000] {
+            let p = BigInt::new("2").pow(n);
+            assert_eq!(p.bits(), n as u64 + 1);
+            assert_eq!((p - BigInt::new("1")).bits(), n as u64);
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(500);
+            assert_eq!(BigInt::new(format!("{}", v).as_str()).bits(), v.bits());
+        }
+    }
+
+    #[test]
+    fn test_num_digits() {
+        let zero = BigInt::new("-000");
+        assert_eq!(zero.num_digits(), 1);
+        assert_eq!(zero.digit_at(0), Some(0));
+        assert_eq!(zero.digit_at(1), None);
+
+        let v = BigInt::new("-7");
+        assert_eq!(v.num_digits(), 1);
+        assert_eq!(v.digit_at(0), Some(7));
+
+        let v = BigInt::new("00123456789012345678901234567890");
+        assert_eq!(v.num_digits(), 30);
+        assert_eq!(v.digit_at(0), Some(0));
+        assert_eq!(v.digit_at(1), Some(9));
+        assert_eq!(v.digit_at(29), Some(1));
+        assert_eq!(v.digit_at(30), None);
+        assert_eq!(BigInt::new("10").pow(100).num_digits(), 101);
+    }
+
+    #[test]
+    fn test_default() {
+        assert!(BigInt::default() == BigInt::new("0"));
+        assert!(!BigInt::default().is_negative());
+        assert_eq!(format!("{}", BigInt::default()), "0".to_string());
+
+        #[derive(Default)]
+        struct Account {
+            balance: BigInt,
+        }
+        assert!(Account::default().balance.is_zero());
+
+        let mut values = vec![BigInt::new("1")];
+        values.resize_with(3, Default::default);
+        assert!(values[2] == BigInt::new("0"));
+    }
+
+    #[test]
+    fn test_zero_one() {
+        assert!(BigInt::zero() == BigInt::new("0"));
+        assert!(BigInt::zero() == BigInt::new("-0"));
+        assert!(BigInt::one() == BigInt::new("1"));
+        assert!(BigInt::zero().is_zero());
+        assert!(BigInt::one().is_positive());
+        assert!(BigInt::one() * BigInt::new("-5") == BigInt::new("-5"));
+        assert!(BigInt::new("-5") + BigInt::zero() == BigInt::new("-5"));
+    }
+
+    #[test]
+    fn test_debug_format() {
+        assert_eq!(format!("{:?}", BigInt::new("-123")), "BigInt { positive: false, value: [123] }".to_string());
+        assert_eq!(format!("{:?}", BigInt::new("-0")), "BigInt { positive: true, value: [0] }".to_string());
+        assert_eq!(format!("{:?}", BigInt::new("1234567890123")),
+                   "BigInt { positive: true, value: [567890123, 1234] }".to_string());
+        assert_eq!(format!("{:?}", BigInt { positive: true, value: vec![5, 0, 0] }),
+                   "BigInt { positive: true, value: [5, 0, 0] }".to_string());
+        assert_eq!(BigInt::new("42"), BigInt::from(42));
+    }
+
+    #[test]
+    fn test_factorial_binomial() {
+        assert_eq!(format!("{}", BigInt::factorial(0)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::factorial(1)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::factorial(20)), "2432902008176640000".to_string());
+        assert_eq!(format!("{}", BigInt::factorial(30)), "265252859812191058636308480000000".to_string());
+
+        assert_eq!(format!("{}", BigInt::binomial(52, 5)), "2598960".to_string());
+        assert_eq!(format!("{}", BigInt::binomial(5, 0)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::binomial(5, 5)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::binomial(5, 6)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::binomial(0, 0)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::binomial(100, 50)), "100891344545564193334812497256".to_string());
+        assert!(BigInt::binomial(40, 17) ==
+            BigInt::factorial(40) / (BigInt::factorial(17) * BigInt::factorial(23)));
+    }
+
+    #[test]
+    fn test_primitive_comparator() {
+        let n = BigInt::new("150");
+        assert!(n > 100);
+        assert!(n >= 150);
+        assert!(n == 150);
+        assert!(n != -150);
+        assert!(n < 151);
+        assert!(100 < n);
+        assert!(150 == n);
+        assert!(151 > n);
+
+        let n = BigInt::new("-42");
+        assert!(n < 0);
+        assert!(n > -43);
+        assert!(n == -42);
+        assert!(-42 == n);
+        assert!(0 > n);
+
+        assert!(BigInt::new("-0") == 0);
+        let (max, min) = (BigInt::from(i64::MAX), BigInt::from(i64::MIN));
+        assert!(max == i64::MAX);
+        assert!(min == i64::MIN);
+        assert!(max + BigInt::one() > i64::MAX);
+        assert!(min.clone() - BigInt::one() < i64::MIN);
+        assert!(i64::MIN > min - BigInt::one());
+    }
+
+    #[test]
+    fn test_sub_single_digit() {
+        let sub = |a: &str, b: &str| BigInt::new(a) - BigInt::new(b);
+
+        assert_eq!(sub("8", "3"), BigInt::new("5"));
+        assert_eq!(sub("3", "8"), BigInt::new("-5"));
+        assert_eq!(sub("-3", "-8"), BigInt::new("5"));
+        assert_eq!(sub("-8", "-3"), BigInt::new("-5"));
+        assert_eq!(sub("9", "0"), BigInt::new("9"));
+        assert_eq!(sub("0", "9"), BigInt::new("-9"));
+        for (a, b) in [("9", "9"), ("-9", "-9"), ("0", "0"), ("0", "-0")] {
+            let diff = sub(a, b);
+            assert_eq!(diff, BigInt::zero());
+            assert!(diff.is_zero() && !diff.is_negative());
+        }
+
+        let mut v = BigInt::new("9");
+        v -= BigInt::new("9");
+        assert_eq!(v, BigInt::zero());
+        assert_eq!(-(BigInt::new("9") + -BigInt::new("9")), BigInt::zero());
+
+        for a in -9i64..=9 {
+            for b in -9i64..=9 {
+                let diff = BigInt::from(a) - BigInt::from(b);
+                assert_eq!(diff, BigInt::from(a - b), "{} - {}", a, b);
+                assert_eq!(format!("{}", diff), (a - b).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_trim_zero() {
+        let mut v = BigInt { positive: false, value: vec![0, 0, 0] };
+        v.trim_zero();
+        assert_eq!(v, BigInt::zero());
+        assert_eq!(format!("{}", v), "0".to_string());
+
+        let mut v = BigInt { positive: false, value: vec![] };
+        v.trim_zero();
+        assert_eq!(v, BigInt::zero());
+
+        let mut v = BigInt { positive: false, value: vec![5, 0, 0] };
+        v.trim_zero();
+        assert_eq!(v, BigInt::new("-5"));
+
+        for (a, b) in [("123", "123"), ("-1000", "-1000"), ("99999", "99999")] {
+            let diff = BigInt::new(a) - BigInt::new(b);
+            assert_eq!(format!("{}", diff), "0".to_string());
+            assert_eq!(diff, BigInt::new("0"));
+        }
+        let (q, r) = BigInt::new("-1000").div_rem(BigInt::new("10"));
+        assert_eq!(q, BigInt::new("-100"));
+        assert_eq!(r, BigInt::new("0"));
+        let (q, r) = BigInt::new("-7").div_rem(BigInt::new("10"));
+        assert_eq!(format!("{} {}", q, r), "0 -7".to_string());
+        assert_eq!(BigInt::new("0") / BigInt::new("-1"), BigInt::zero());
+        assert_eq!(BigInt::new("-123") * BigInt::new("0"), BigInt::zero());
+    }
+
+    #[test]
+    fn test_extended_gcd_modinv() {
+        for (a, b) in [(240i64, 46i64), (-240, 46), (240, -46), (-240, -46), (0, 5), (5, 0), (0, 0), (17, 5)] {
+            let (g, x, y) = BigInt::from(a).extended_gcd(BigInt::from(b));
+            assert_eq!(g, BigInt::from(a).gcd(BigInt::from(b)));
+            assert_eq!(BigInt::from(a) * x + BigInt::from(b) * y, g);
+        }
+
+        assert_eq!(BigInt::new("3").modinv(&BigInt::new("11")), Some(BigInt::new("4")));
+        assert_eq!(BigInt::new("-3").modinv(&BigInt::new("11")), Some(BigInt::new("7")));
+        assert_eq!(BigInt::new("10").modinv(&BigInt::new("17")), Some(BigInt::new("12")));
+        assert_eq!(BigInt::new("5").modinv(&BigInt::new("1")), Some(BigInt::new("0")));
+        assert_eq!(BigInt::new("6").modinv(&BigInt::new("9")), None);
+        assert_eq!(BigInt::new("0").modinv(&BigInt::new("7")), None);
+        assert!(catch_unwind(|| BigInt::new("3").modinv(&BigInt::new("0"))).is_err());
+
+        let m = BigInt::new("1000000007");
+        for a in ["2", "123456789", "-987654321", "99999999999999999999"] {
+            let a = BigInt::new(a);
+            let inverse = a.clone().modinv(&m).unwrap();
+            assert!(inverse >= BigInt::zero() && inverse < m);
+            assert!(((a * inverse - BigInt::one()) % m.clone()).is_zero());
+        }
+    }
+
+    #[test]
+    fn test_is_probably_prime() {
+        for p in ["2", "3", "5", "37", "41", "97", "7919", "1000000007", "2305843009213693951",
+            "170141183460469231731687303715884105727"] {
+            assert!(BigInt::new(p).is_probably_prime(20), "{}", p);
+        }
+        for c in ["-7", "0", "1", "4", "9", "91", "561", "1105", "1729", "41041", "1000000005",
+            "2305843009213693953", "170141183460469231731687303715884105729"] {
+            assert!(!BigInt::new(c).is_probably_prime(20), "{}", c);
+        }
+
+        // 两个大素数之积
+        let product = BigInt::new("1000000007") * BigInt::new("2305843009213693951");
+        assert!(!product.is_probably_prime(20));
+    }
+
+    #[test]
+    fn test_reduce_mod() {
+        let m = BigInt::new("7");
+        for (v, expected) in [(-15, 6), (-7, 0), (-1, 6), (0, 0), (3, 3), (6, 6), (7, 0), (100, 2)] {
+            assert_eq!(BigInt::from(v).reduce_mod(&m), BigInt::from(expected));
+        }
+
+        let m = BigInt::new("123456789012345678901234567890");
+        let inside = BigInt::new("98765432109876543210");
+        assert_eq!(inside.clone().reduce_mod(&m), inside);
+        assert_eq!((inside.clone() + m.clone() * BigInt::new("5")).reduce_mod(&m), inside);
+        assert_eq!((inside.clone() - m.clone() * BigInt::new("5")).reduce_mod(&m), inside);
+        assert!(!(-m.clone()).reduce_mod(&m).is_negative());
+        assert!(catch_unwind(|| BigInt::new("5").reduce_mod(&BigInt::new("0"))).is_err());
+        assert!(catch_unwind(|| BigInt::new("5").reduce_mod(&BigInt::new("-7"))).is_err());
+    }
+
+    #[test]
+    fn test_div_rem_euclid() {
+        for a in [-17i64, -15, -5, -1, 0, 1, 5, 15, 17] {
+            for b in [-5i64, -3, -1, 1, 3, 5] {
+                let q = BigInt::from(a).div_euclid(BigInt::from(b));
+                let r = BigInt::from(a).rem_euclid(BigInt::from(b));
+                assert_eq!(q, BigInt::from(a.div_euclid(b)), "{} div_euclid {}", a, b);
+                assert_eq!(r, BigInt::from(a.rem_euclid(b)), "{} rem_euclid {}", a, b);
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(100)).as_str());
+            if b.is_zero() { continue; }
+
+            let q = a.clone().div_euclid(b.clone());
+            let r = a.clone().rem_euclid(b.clone());
+            assert!(!r.is_negative() && r < b.abs());
+            assert_eq!(q * b + r, a);
+        }
+        assert!(catch_unwind(|| BigInt::new("1").rem_euclid(BigInt::new("0"))).is_err());
+    }
+
+    #[test]
+    fn test_div_floor_ceil() {
+        assert_eq!(BigInt::new("-7").div_floor(BigInt::new("2")), BigInt::new("-4"));
+        assert_eq!(BigInt::new("7").div_ceil(BigInt::new("2")), BigInt::new("4"));
+
+        for (a, b, floor, ceil) in [
+            (7i64, 2i64, 3i64, 4i64),
+            (-7, 2, -4, -3),
+            (7, -2, -4, -3),
+            (-7, -2, 3, 4),
+            (6, 2, 3, 3),
+            (-6, 2, -3, -3),
+            (6, -2, -3, -3),
+            (-6, -2, 3, 3),
+            (0, 5, 0, 0),
+            (1, 5, 0, 1),
+            (-1, 5, -1, 0),
+        ] {
+            assert_eq!(BigInt::from(a).div_floor(BigInt::from(b)), BigInt::from(floor), "{} div_floor {}", a, b);
+            assert_eq!(BigInt::from(a).div_ceil(BigInt::from(b)), BigInt::from(ceil), "{} div_ceil {}", a, b);
+        }
+
+        let a = BigInt::new("-100000000000000000001");
+        let b = BigInt::new("10");
+        assert_eq!(a.clone().div_floor(b.clone()), BigInt::new("-10000000000000000001"));
+        assert_eq!(a.div_ceil(b), BigInt::new("-10000000000000000000"));
+    }
+
+    #[test]
+    fn test_display_padding() {
+        assert_eq!(format!("{:>10}", BigInt::new("42")), format!("{:>10}", 42));
+        assert_eq!(format!("{:<10}|", BigInt::new("42")), format!("{:<10}|", 42));
+        assert_eq!(format!("{:^10}|", BigInt::new("-42")), format!("{:^10}|", -42));
+        assert_eq!(format!("{:*>8}", BigInt::new("-42")), "*****-42".to_string());
+        assert_eq!(format!("{:10}", BigInt::new("-42")), format!("{:10}", -42));
+        assert_eq!(format!("{:08}", BigInt::new("42")), "00000042".to_string());
+        assert_eq!(format!("{:08}", BigInt::new("-42")), "-0000042".to_string());
+        assert_eq!(format!("{:08}", BigInt::new("0")), "00000000".to_string());
+        assert_eq!(format!("{:2}", BigInt::new("-12345")), "-12345".to_string());
+
+        for v in [-1234567i64, -1, 0, 7, 98765] {
+            assert_eq!(format!("{:>12}", BigInt::from(v)), format!("{:>12}", v));
+            assert_eq!(format!("{:<12}", BigInt::from(v)), format!("{:<12}", v));
+            assert_eq!(format!("{:^12}", BigInt::from(v)), format!("{:^12}", v));
+            assert_eq!(format!("{:012}", BigInt::from(v)), format!("{:012}", v));
+        }
+    }
+
+    #[test]
+    fn test_display_sign_plus() {
+        assert_eq!(format!("{:+}", BigInt::new("5")), "+5".to_string());
+        assert_eq!(format!("{:+}", BigInt::new("-5")), "-5".to_string());
+        assert_eq!(format!("{:+}", BigInt::new("0")), "+0".to_string());
+        assert_eq!(format!("{:+}", BigInt::new("-0")), format!("{:+}", 0));
+        assert_eq!(format!("{:+06}", BigInt::new("42")), "+00042".to_string());
+        assert_eq!(format!("{:>+6}", BigInt::new("42")), "   +42".to_string());
+        assert_eq!(format!("{:+x}", BigInt::new("255")), "+ff".to_string());
+        assert_eq!(format!("{:+#b}", BigInt::new("-2")), "-0b10".to_string());
+
+        for v in [-1234567i64, -1, 0, 1, 98765] {
+            assert_eq!(format!("{:+}", BigInt::from(v)), format!("{:+}", v));
+            assert_eq!(format!("{:+010}", BigInt::from(v)), format!("{:+010}", v));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_into_num_bigint() {
+        for v in ["0", "-0", "7", "-7", "123456789012345678901234567890", "-98765432109876543210"] {
+            let converted = num_bigint::BigInt::from(BigInt::new(v));
+            assert_eq!(converted, v.parse::<num_bigint::BigInt>().unwrap());
+            assert_eq!(BigInt::new(converted.to_string().as_str()), BigInt::new(v));
+        }
+        assert_eq!(num_bigint::BigInt::from(BigInt::new("-0")).sign(), num_bigint::Sign::NoSign);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(500);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            assert_eq!(num_bigint::BigInt::from(tested), v);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_from_num_bigint() {
+        assert_eq!(format!("{}", BigInt::from(num_bigint::BigInt::from(0))), "0".to_string());
+        assert!(!BigInt::from(num_bigint::BigInt::from(0)).is_negative());
+        assert_eq!(format!("{}", BigInt::from(num_bigint::BigInt::from(-42))), "-42".to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let v = rng.gen_bigint(500);
+            let converted = BigInt::from(v.clone());
+            assert_eq!(format!("{}", converted), format!("{}", v));
+            assert_eq!(num_bigint::BigInt::from(converted), v);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-traits")]
+    fn test_num_traits() {
+        use std::ops::{Add, Mul};
+
+        fn dot<T: num_traits::Zero + num_traits::One + Add<Output=T> + Mul<Output=T> + Clone>(a: &[T], b: &[T]) -> T {
+            a.iter().zip(b).fold(T::zero(), |acc, (x, y)| acc + x.clone() * y.clone())
+        }
+
+        fn power<T: num_traits::One + Mul<Output=T> + Clone>(base: T, exp: u32) -> T {
+            (0..exp).fold(T::one(), |acc, _| acc * base.clone())
+        }
+
+        assert_eq!(<BigInt as num_traits::Zero>::zero(), BigInt::zero());
+        assert_eq!(<BigInt as num_traits::One>::one(), BigInt::one());
+        assert!(num_traits::Zero::is_zero(&BigInt::new("-0")));
+        assert!(!num_traits::Zero::is_zero(&BigInt::new("7")));
+
+        let a = [BigInt::new("1"), BigInt::new("-2"), BigInt::new("30000000000000000000")];
+        let b = [BigInt::new("4"), BigInt::new("5"), BigInt::new("6")];
+        assert_eq!(dot(&a, &b), BigInt::new("179999999999999999994"));
+        assert_eq!(dot::<BigInt>(&[], &[]), BigInt::zero());
+        assert_eq!(power(BigInt::new("-3"), 5), BigInt::new("-243"));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_random() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(BigInt::random(0, &mut rng), BigInt::zero());
+
+        let mut seen_short = false;
+        for _ in 0..200 {
+            let v = BigInt::random(30, &mut rng);
+            assert!(!v.is_negative());
+            assert!(v.num_digits() <= 30);
+            assert!(v.num_digits() == 1 || v.digits().next() != Some(0));
+            seen_short |= v.num_digits() < 30;
+        }
+        assert!(seen_short);
+
+        let mut digits = [false; 10];
+        for _ in 0..200 {
+            let v = BigInt::random(1, &mut rng);
+            digits[v.digits().next().unwrap() as usize] = true;
+        }
+        assert!(digits.iter().all(|&d| d));
+
+        let (low, high) = (BigInt::new("-1000000000000000000000"), BigInt::new("-999999999999999999990"));
+        for _ in 0..100 {
+            let v = BigInt::random_range(&low, &high, &mut rng);
+            assert!(v >= low && v < high);
+        }
+        let (low, high) = (BigInt::new("-50"), BigInt::new("50"));
+        let mut hit_low = false;
+        for _ in 0..2000 {
+            let v = BigInt::random_range(&low, &high, &mut rng);
+            assert!(v >= low && v < high);
+            hit_low |= v == low;
+        }
+        assert!(hit_low);
+        assert_eq!(BigInt::random_range(&BigInt::new("7"), &BigInt::new("8"), &mut rng), BigInt::new("7"));
+        assert!(catch_unwind(|| BigInt::random_range(&BigInt::one(), &BigInt::one(), &mut rand::thread_rng())).is_err());
+    }
+
+    #[test]
+    fn test_saturating_to_primitive() {
+        let one = BigInt::one();
+        let (max, min) = (BigInt::from(i64::MAX), BigInt::from(i64::MIN));
+
+        assert_eq!(BigInt::new("0").to_i64_saturating(), 0);
+        assert_eq!(BigInt::new("-123").to_i64_saturating(), -123);
+        assert_eq!(max.to_i64_saturating(), i64::MAX);
+        assert_eq!(min.to_i64_saturating(), i64::MIN);
+        assert_eq!((max.clone() + one.clone()).to_i64_saturating(), i64::MAX);
+        assert_eq!((min.clone() - one.clone()).to_i64_saturating(), i64::MIN);
+        assert_eq!(BigInt::new("10").pow(100).to_i64_saturating(), i64::MAX);
+        assert_eq!((-BigInt::new("10").pow(100)).to_i64_saturating(), i64::MIN);
+
+        assert_eq!(BigInt::new("0").to_u64_saturating(), 0);
+        assert_eq!(BigInt::new("123").to_u64_saturating(), 123);
+        assert_eq!(BigInt::new("-1").to_u64_saturating(), 0);
+        assert_eq!(min.to_u64_saturating(), 0);
+        assert_eq!((max + one.clone()).to_u64_saturating(), i64::MAX as u64 + 1);
+        assert_eq!(BigInt::from(u64::MAX).to_u64_saturating(), u64::MAX);
+        assert_eq!((BigInt::from(u64::MAX) + one).to_u64_saturating(), u64::MAX);
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(BigInt::new("0").to_f64(), 0.0);
+        assert_eq!(BigInt::new("-12345").to_f64(), -12345.0);
+        assert_eq!(BigInt::from(1u64 << 53).to_f64(), 9007199254740992.0);
+        assert_eq!(BigInt::new("10").pow(20).to_f64(), 1e20);
+        assert_eq!(BigInt::new("10").pow(308).to_f64(), 1e308);
+        assert_eq!(BigInt::new("10").pow(309).to_f64(), f64::INFINITY);
+        assert_eq!((-BigInt::new("10").pow(400)).to_f64(), f64::NEG_INFINITY);
+
+        assert_eq!(BigInt::new("123456789012345678999999").to_f64(), 1.2345678901234569e23);
+        // 恰在两个 f64 中点时向偶数舍入，截断前 17 位会丢掉其后的非零位
+        assert_eq!(BigInt::from((1u64 << 53) + 1).to_f64(), 9007199254740992.0);
+        assert_eq!(BigInt::new("9223372036854776832").to_f64(), 9223372036854775808.0);
+        assert_eq!(BigInt::new("9223372036854776833").to_f64(), 9223372036854777856.0);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..2000 {
+            let v: u128 = rng.gen::<u128>() >> rng.gen_range(0..68);
+            assert_eq!(BigInt::from(v).to_f64(), v as f64);
+            assert_eq!(BigInt::from(v as i128).to_f64(), v as i128 as f64);
+        }
+        for _ in 0..100 {
+            let v = rng.gen_bigint(3000);
+            let expected: f64 = format!("{}", v).parse().unwrap();
+            assert_eq!(BigInt::new(format!("{}", v).as_str()).to_f64(), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(BigInt::from_f64(0.0), Some(BigInt::zero()));
+        assert_eq!(BigInt::from_f64(-0.0), Some(BigInt::zero()));
+        assert_eq!(BigInt::from_f64(1.0), Some(BigInt::one()));
+        assert_eq!(BigInt::from_f64(1e20), Some(BigInt::new("100000000000000000000")));
+        assert_eq!(BigInt::from_f64(9007199254740993.0), Some(BigInt::new("9007199254740992")));
+        assert_eq!(BigInt::from_f64(3.99), Some(BigInt::new("3")));
+        assert_eq!(BigInt::from_f64(0.5), Some(BigInt::zero()));
+        assert_eq!(BigInt::from_f64(f64::MIN_POSITIVE), Some(BigInt::zero()));
+        assert_eq!(BigInt::from_f64(-3.99), Some(BigInt::new("-3")));
+        assert_eq!(BigInt::from_f64(-1e20), Some(BigInt::new("-100000000000000000000")));
+        assert_eq!(BigInt::from_f64(f64::MAX), Some(BigInt::new("2").pow(1024) - BigInt::new("2").pow(971)));
+        assert_eq!(BigInt::from_f64(f64::NAN), None);
+        assert_eq!(BigInt::from_f64(f64::INFINITY), None);
+        assert_eq!(BigInt::from_f64(f64::NEG_INFINITY), None);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let v: f64 = rng.gen_range(1.0..1e30) * if rng.gen() { 1.0 } else { -1.0 };
+            // f64 的 {:.0} 会输出整数部分的精确十进制展开
+            assert_eq!(format!("{}", BigInt::from_f64(v).unwrap()), format!("{:.0}", v.trunc()));
+        }
+    }
+
+    #[test]
+    fn test_increment_decrement() {
+        let mut v = BigInt::new("-1001");
+        for i in -1000..=1000 {
+            v.increment();
+            assert_eq!(v, BigInt::from(i));
+        }
+        for i in (-1001..1000).rev() {
+            v.decrement();
+            assert_eq!(v, BigInt::from(i));
+        }
+
+        let mut v = BigInt::new("-1");
+        v.increment();
+        assert!(v.is_zero() && !v.is_negative());
+        assert_eq!(format!("{}", v), "0".to_string());
+        v.decrement();
+        assert_eq!(format!("{}", v), "-1".to_string());
+
+        let mut v = BigInt::new("99999999999999999999");
+        v.increment();
+        assert_eq!(format!("{}", v), "100000000000000000000".to_string());
+        v.decrement();
+        assert_eq!(format!("{}", v), "99999999999999999999".to_string());
+        let mut v = BigInt::new("-99999999999999999999");
+        v.decrement();
+        assert_eq!(format!("{}", v), "-100000000000000000000".to_string());
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let (a, b) = (BigInt::new("-12345678901234567890"), BigInt::new("98765"));
+        assert_eq!(a.clone().min(b.clone()), a);
+        assert_eq!(a.clone().max(b.clone()), b);
+        assert_eq!(b.clone().min(a.clone()), a);
+        assert_eq!(b.clone().max(a.clone()), b);
+        assert_eq!(a.clone().min(a.clone()), a);
+
+        let (low, high) = (BigInt::from(-100), BigInt::from(100));
+        for (v, expected) in [(-1000, -100), (-101, -100), (-100, -100), (-5, -5), (0, 0), (42, 42), (100, 100), (101, 100), (1000, 100)] {
+            assert_eq!(BigInt::from(v).clamp(low.clone(), high.clone()), BigInt::from(expected));
+        }
+
+        let (low, high) = (BigInt::from(-300), BigInt::from(-200));
+        assert_eq!(BigInt::new("-99999999999999999999").clamp(low.clone(), high.clone()), low);
+        assert_eq!(BigInt::from(-250).clamp(low.clone(), high.clone()), BigInt::from(-250));
+        assert_eq!(BigInt::from(7).clamp(low, high.clone()), high);
+    }
+
+    #[test]
+    fn test_mul_single_digit() {
+        let mul = |a: &str, b: &str| format!("{}", BigInt::new(a) * BigInt::new(b));
+        assert_eq!(mul("1", "-98765432109876543210"), "-98765432109876543210".to_string());
+        assert_eq!(mul("-98765432109876543210", "-1"), "98765432109876543210".to_string());
+        assert_eq!(mul("-7", "8"), "-56".to_string());
+        assert_eq!(mul("9", "-99999999999999999999"), "-899999999999999999991".to_string());
+        assert_eq!(mul("0", "-5"), "0".to_string());
+        assert_eq!(mul("-5", "0"), "0".to_string());
+        assert!(!(BigInt::new("-5") * BigInt::new("0")).is_negative());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let a = rng.gen_bigint(500);
+            let b = rng.gen_bigint_range(&-9.to_bigint().unwrap(), &10.to_bigint().unwrap());
+            let tested_a = BigInt::new(format!("{}", a).as_str());
+            let tested_b = BigInt::new(format!("{}", b).as_str());
+            assert_eq!(format!("{}", tested_a.clone() * tested_b.clone()), format!("{}", a.clone() * b.clone()));
+            assert_eq!(format!("{}", tested_b * tested_a), format!("{}", b * a));
+        }
+    }
+
+    #[test]
+    fn test_mul_small() {
+        assert_eq!(format!("{}", BigInt::new("12345").mul_small(0)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("-12345").mul_small(0)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("0").mul_small(7)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("-99999").mul_small(10)), "-999990".to_string());
+        assert_eq!(format!("{}", BigInt::new("99999").mul_small(u32::MAX)),
+                   (99999u64 * u32::MAX as u64).to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let a = rng.gen_bigint(1000);
+            let factor: u32 = if rng.gen() { rng.gen_range(0..10) } else { rng.gen() };
+            let tested_a = BigInt::new(format!("{}", a).as_str());
+            let expected = tested_a.clone() * BigInt::from(factor);
+            assert_eq!(tested_a.mul_small(factor), expected);
+            assert_eq!(format!("{}", tested_a.mul_small(factor)), format!("{}", a * factor));
+        }
+    }
+
+    #[test]
+    fn test_div_rem_small() {
+        let (quotient, remainder) = BigInt::new("-12345").div_rem_small(100);
+        assert_eq!(format!("{}", quotient), "-123".to_string());
+        assert_eq!(remainder, 45);
+        let (quotient, remainder) = BigInt::new("-7").div_rem_small(10);
+        assert!(quotient.is_zero() && !quotient.is_negative());
+        assert_eq!(remainder, 7);
+        let (quotient, remainder) = BigInt::new("0").div_rem_small(3);
+        assert!(quotient.is_zero());
+        assert_eq!(remainder, 0);
+
+        let mut rng = rand::thread_rng();
+        for divisor in [1, 2, 3, 7, 10, 16, 36, 256, 1_000_000_007, u32::MAX] {
+            for _ in 0..50 {
+                let a = BigInt::new(format!("{}", rng.gen_bigint(500)).as_str());
+                let (quotient, remainder) = a.div_rem_small(divisor);
+                let (expected_quotient, expected_remainder) = a.clone().div_rem(BigInt::from(divisor));
+                assert_eq!(quotient, expected_quotient);
+                assert_eq!(BigInt::from(remainder), expected_remainder.abs());
+            }
         }
     }
 
-    fn abs(self) -> Self {
-        Self {
-            positive: true,
-            value: self.value.clone(),
-        }
+    #[test]
+    #[should_panic(expected = "divisor can't be 0")]
+    fn test_div_rem_small_by_zero() {
+        BigInt::new("42").div_rem_small(0);
     }
 
-    fn trim_zero(&mut self) {
-        let mut i = 0usize;
-        for v in self.value.iter() {
-            if v != &0 { break; }
-            i += 1;
+    #[test]
+    fn test_mul_div_pow2() {
+        assert_eq!(format!("{}", BigInt::new("-3").mul_pow2(4)), "-48".to_string());
+        assert_eq!(format!("{}", BigInt::new("0").mul_pow2(100)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::new("7").mul_pow2(0)), "7".to_string());
+        assert_eq!(format!("{}", BigInt::new("-5").div_pow2(1)), "-2".to_string());
+        assert_eq!(format!("{}", BigInt::new("-5").div_pow2(10)), "0".to_string());
+        assert!(!BigInt::new("-5").div_pow2(10).is_negative());
+        assert_eq!(format!("{}", BigInt::new("48").div_pow2(4)), "3".to_string());
+        assert_eq!(BigInt::new("5").div_pow2(1000), BigInt::zero());
+        assert_eq!(BigInt::new("-5") >> 1000, BigInt::new("-1"));
+        let power = BigInt::from(2u32).pow(100);
+        assert_eq!(power.div_pow2(100), BigInt::one());
+        assert_eq!((power.clone() - BigInt::one()).div_pow2(100), BigInt::zero());
+        assert_eq!(-power.clone() >> 100, BigInt::new("-1"));
+        assert_eq!((-power - BigInt::one()) >> 100, BigInt::new("-2"));
+
+        let mut rng = rand::thread_rng();
+        let v = BigInt::new(format!("{}", rng.gen_bigint(3000)).as_str());
+        for n in [1u32, 31, 32, 1000, 5000] {
+            let power = BigInt::from(2u32).pow(n);
+            let shifted = v.mul_pow2(n);
+            assert_eq!(shifted, v.clone() * power.clone());
+            let halved = shifted.div_pow2(n);
+            assert_eq!(halved, shifted.clone() / power.clone());
+            assert_eq!(halved, v);
+            assert_eq!(v.div_pow2(n), v.clone() / power);
         }
-        self.value.drain(0..i);
     }
-}
 
-impl ops::Neg for BigInt {
-    type Output = Self;
+    // cargo test --release -- --ignored --nocapture 查看耗时
+    #[test]
+    #[ignore]
+    fn bench_mul_div_pow2() {
+        let mut rng = rand::thread_rng();
+        let v = BigInt::new(format!("{}", rng.gen_bigint(10000)).as_str());
+        for n in [31u32, 1000, 10000, 50000] {
+            let start = Instant::now();
+            let shifted = v.mul_pow2(n);
+            let shift_elapsed = start.elapsed();
+            let start = Instant::now();
+            let mut chunked = v.clone();
+            for _ in 0..n / 31 {
+                chunked = chunked.mul_small(1 << 31);
+            }
+            let chunked = chunked.mul_small(1 << (n % 31));
+            let chunked_elapsed = start.elapsed();
+            let start = Instant::now();
+            let power = BigInt::from(2u32).pow(n);
+            let expected = v.clone() * power.clone();
+            let pow_elapsed = start.elapsed();
+            assert_eq!(shifted, expected);
+            assert_eq!(chunked, expected);
+            println!("mul_pow2({}): {:?}, mul_small(2^31) passes: {:?}, * pow(2, n): {:?}",
+                     n, shift_elapsed, chunked_elapsed, pow_elapsed);
 
-    fn neg(self) -> Self::Output {
-        if self.value == vec![0] {
-            return self.clone();
+            let start = Instant::now();
+            let halved = shifted.div_pow2(n);
+            let shift_elapsed = start.elapsed();
+            let start = Instant::now();
+            let expected = shifted.clone() / power;
+            let pow_elapsed = start.elapsed();
+            assert_eq!(halved, expected);
+            println!("div_pow2({}): {:?}, / pow(2, n): {:?}", n, shift_elapsed, pow_elapsed);
         }
+    }
 
-        Self {
-            positive: !self.positive,
-            value: self.value.clone(),
+    #[test]
+    fn test_shift_decimal() {
+        let v = BigInt::new("-12345");
+        assert_eq!(format!("{}", v.shift_decimal_left(0)), "-12345".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_left(3)), "-12345000".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_right(0)), "-12345".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_right(2)), "-123".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_right(4)), "-1".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_right(5)), "0".to_string());
+        assert_eq!(format!("{}", v.shift_decimal_right(100)), "0".to_string());
+        assert!(!v.shift_decimal_right(5).is_negative());
+
+        let zero = BigInt::new("0");
+        assert_eq!(format!("{}", zero.shift_decimal_left(10)), "0".to_string());
+        assert_eq!(format!("{}", zero.shift_decimal_right(10)), "0".to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let n = rng.gen_range(0..120);
+            let scale = BigInt::from(10).pow(n as u32);
+            assert_eq!(a.shift_decimal_left(n), a.clone() * scale.clone());
+            assert_eq!(a.shift_decimal_right(n), a.clone() / scale);
+            assert_eq!(a.shift_decimal_left(n).shift_decimal_right(n), a);
         }
     }
-}
 
-impl ops::Add<BigInt> for BigInt {
-    type Output = Self;
+    #[test]
+    fn test_power_of_two() {
+        assert!(!BigInt::new("0").is_power_of_two());
+        assert!(!BigInt::new("-2").is_power_of_two());
+        assert!(!BigInt::new("6").is_power_of_two());
+        assert!(BigInt::new("1").is_power_of_two());
+        assert_eq!(format!("{}", BigInt::new("0").next_power_of_two()), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("-100").next_power_of_two()), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("1").next_power_of_two()), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("3").next_power_of_two()), "4".to_string());
 
-    fn add(self, rhs: Self) -> Self::Output {
-        if self.positive != rhs.positive {
-            return if self.positive {
-                self - (-rhs)
-            } else {
-                rhs - (-self)
-            };
+        let one = BigInt::new("1");
+        for n in [2u32, 7, 8, 9, 63, 64, 65, 127, 128, 1000] {
+            let p = BigInt::new("2").pow(n);
+            let below = p.clone() - one.clone();
+            let above = p.clone() + one.clone();
+            assert!(p.is_power_of_two());
+            assert!(!below.is_power_of_two());
+            assert!(!above.is_power_of_two());
+            assert_eq!(p.next_power_of_two(), p);
+            assert_eq!(below.next_power_of_two(), p);
+            assert_eq!(above.next_power_of_two(), p.clone() * BigInt::new("2"));
         }
+    }
 
-        // 以下同号
-        let (mut longer, mut shorter) = (&self, &rhs);
-        if self.value.len() < rhs.value.len() {
-            longer = &rhs;
-            shorter = &self;
+    #[test]
+    fn test_trailing_zeros() {
+        assert_eq!(BigInt::new("0").trailing_zeros(), None);
+        assert_eq!(BigInt::new("-0").trailing_zeros(), None);
+        assert_eq!(BigInt::new("1").trailing_zeros(), Some(0));
+        assert_eq!(BigInt::new("12").trailing_zeros(), Some(2));
+        assert_eq!(BigInt::new("-12").trailing_zeros(), Some(2));
+        assert_eq!(BigInt::new("12345678901234567890123456789").trailing_zeros(), Some(0));
+        for n in [1u32, 7, 8, 9, 63, 64, 65, 1000] {
+            let p = BigInt::new("2").pow(n);
+            assert_eq!(p.trailing_zeros(), Some(n as u64));
+            assert_eq!((p.clone() * BigInt::new("3")).trailing_zeros(), Some(n as u64));
+            assert_eq!((p + BigInt::new("1")).trailing_zeros(), Some(0));
         }
-        let mut sum = Self {
-            positive: self.positive,
-            value: vec![0; longer.value.len() + 1],
-        };
 
-        let mut end = longer.value.len();
-        for (i, j) in (0..end).rev()
-            .zip((0..shorter.value.len()).rev()) {
-            let k = i + 1;
-            let s = longer.value[i] + shorter.value[j] + sum.value[k];
-            sum.value[k] = s % 10;
-            sum.value[i] = s / 10;
-            end = i;
-        }
-        for i in (0..end).rev() {
-            let k = i + 1;
-            let s = longer.value[i] + sum.value[k];
-            sum.value[k] = s % 10;
-            sum.value[i] = s / 10;
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(500);
+            assert_eq!(BigInt::new(format!("{}", v).as_str()).trailing_zeros(), v.trailing_zeros());
         }
+    }
 
-        sum.trim_zero();
-        sum
+    #[test]
+    fn test_from_iter_digits() {
+        assert_eq!([1u8, 2, 3].into_iter().collect::<BigInt>(), BigInt::new("123"));
+        assert_eq!([0u8, 0, 4, 0].into_iter().collect::<BigInt>(), BigInt::new("40"));
+        assert_eq!([0u8, 0].into_iter().collect::<BigInt>(), BigInt::new("0"));
+        assert_eq!(std::iter::empty::<u8>().collect::<BigInt>(), BigInt::new("0"));
+        assert_eq!(std::iter::repeat_n(9u8, 30).collect::<BigInt>(), BigInt::new("999999999999999999999999999999"));
+        assert!(catch_unwind(|| [1u8, 10, 3].into_iter().collect::<BigInt>()).is_err());
     }
-}
 
-impl ops::Sub<BigInt> for BigInt {
-    type Output = Self;
+    #[test]
+    fn test_digits() {
+        assert_eq!(BigInt::new("0").digits().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(BigInt::new("-0").digits().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(BigInt::new("1203").digits().collect::<Vec<_>>(), vec![1, 2, 0, 3]);
+        assert_eq!(BigInt::new("-1203").digits().collect::<Vec<_>>(), vec![1, 2, 0, 3]);
+        assert_eq!(BigInt::new("+000450").digits().collect::<Vec<_>>(), vec![4, 5, 0]);
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        if self == rhs {
-            return Self::new("0");
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let digits: Vec<u8> = v.digits().collect();
+            assert_eq!(digits.len(), v.num_digits());
+            assert_eq!(digits.iter().copied().collect::<BigInt>(), v.abs());
         }
+    }
 
-        if self.positive != rhs.positive {
-            return if self.positive {
-                self + -rhs
-            } else {
-                -(rhs + -self)
-            };
+    #[test]
+    fn test_cmp_magnitude() {
+        let cmp = |a: &str, b: &str| BigInt::new(a).cmp_magnitude(&BigInt::new(b));
+        assert_eq!(cmp("0", "-0"), Ordering::Equal);
+        assert_eq!(cmp("123", "-123"), Ordering::Equal);
+        assert_eq!(cmp("-98765432109876543210", "98765432109876543210"), Ordering::Equal);
+        assert_eq!(cmp("-1000", "999"), Ordering::Greater);
+        assert_eq!(cmp("999", "-1000"), Ordering::Less);
+        assert_eq!(cmp("-124", "-123"), Ordering::Greater);
+        assert_eq!(cmp("0", "-1"), Ordering::Less);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let a = rng.gen_bigint(100);
+            let b = rng.gen_bigint(100);
+            let tested_a = BigInt::new(format!("{}", a).as_str());
+            let tested_b = BigInt::new(format!("{}", b).as_str());
+            assert_eq!(tested_a.cmp_magnitude(&tested_b), a.magnitude().cmp(b.magnitude()));
+            assert_eq!(tested_a.cmp_magnitude(&-&tested_a), Ordering::Equal);
         }
+    }
 
-        if self.value.len() < rhs.value.len() ||
-            (self.value.len() == rhs.value.len()
-                && self.value < rhs.value) {
-            return Self {
-                positive: !self.positive,
-                value: (rhs.abs() - self.abs()).value,
-            };
-        } else if !self.positive {
-            return Self {
-                positive: false,
-                value: (self.abs() - rhs.abs()).value,
-            };
-        };
+    #[test]
+    fn test_try_arithmetic() {
+        let err = |max_digits| Err(DigitLimitError { max_digits });
+        assert_eq!(BigInt::new("999").try_add(BigInt::new("1"), 4), Ok(BigInt::new("1000")));
+        assert_eq!(BigInt::new("999").try_add(BigInt::new("1"), 3), err(3));
+        assert_eq!(BigInt::new("998").try_add(BigInt::new("1"), 3), Ok(BigInt::new("999")));
+        assert_eq!(BigInt::new("-999").try_add(BigInt::new("-1"), 3), err(3));
+        assert_eq!(BigInt::new("12345").try_add(BigInt::new("-12340"), 1), Ok(BigInt::new("5")));
+        assert_eq!(BigInt::new("12345").try_add(BigInt::new("1"), 4), err(4));
 
-        if self.value.len() == 1 {
-            return Self {
-                positive: true,
-                value: vec![self.value[0] - rhs.value[0]],
-            };
-        }
+        assert_eq!(BigInt::new("-999").try_sub(BigInt::new("1"), 4), Ok(BigInt::new("-1000")));
+        assert_eq!(BigInt::new("-999").try_sub(BigInt::new("1"), 3), err(3));
+        assert_eq!(BigInt::new("1000").try_sub(BigInt::new("1"), 3), Ok(BigInt::new("999")));
 
-        // 以下 self > rhs > 0
-        let mut diff = Self {
-            positive: true,
-            value: vec![9; self.value.len() + 1],
-        };
-        diff.value[0] = 0;
-        diff.value[1] = -1;
-        diff.value[self.value.len()] = 10;
-
-        let mut end = self.value.len();
-        for (i, j) in (0..end).rev()
-            .zip((0..rhs.value.len()).rev()) {
-            let k = i + 1;
-            let d = self.value[i] + diff.value[k] - rhs.value[j];
-            diff.value[k] = d % 10;
-            diff.value[i] += d / 10;
-            end = i;
-        }
-        for i in (0..end).rev() {
-            let k = i + 1;
-            let d = self.value[i] + diff.value[k];
-            diff.value[k] = d % 10;
-            diff.value[i] += d / 10;
+        assert_eq!(BigInt::new("99").try_mul(BigInt::new("99"), 4), Ok(BigInt::new("9801")));
+        assert_eq!(BigInt::new("99").try_mul(BigInt::new("99"), 3), err(3));
+        assert_eq!(BigInt::new("10").try_mul(BigInt::new("-10"), 3), Ok(BigInt::new("-100")));
+        assert_eq!(BigInt::new("10").try_mul(BigInt::new("10"), 2), err(2));
+        assert_eq!(BigInt::new("0").try_mul(BigInt::new("123456789"), 1), Ok(BigInt::new("0")));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            for (f, expected) in [
+                (BigInt::try_add as fn(BigInt, BigInt, usize) -> Result<BigInt, DigitLimitError>, a.clone() + b.clone()),
+                (BigInt::try_sub, a.clone() - b.clone()),
+                (BigInt::try_mul, a.clone() * b.clone()),
+            ] {
+                let digits = expected.num_digits();
+                assert_eq!(f(a.clone(), b.clone(), digits), Ok(expected));
+                assert_eq!(f(a.clone(), b.clone(), digits - 1), err(digits - 1));
+            }
         }
 
-        diff.trim_zero();
-        diff
+        assert_eq!(format!("{}", DigitLimitError { max_digits: 10 }), "result would exceed 10 decimal digits");
     }
-}
 
-impl ops::Mul<BigInt> for BigInt {
-    type Output = Self;
+    #[test]
+    fn test_range() {
+        let sum: BigInt = BigInt::range(BigInt::new("1"), BigInt::new("1000")).sum();
+        assert_eq!(format!("{}", sum), "499500".to_string());
+        assert_eq!(BigInt::range(BigInt::new("-10"), BigInt::new("10")).count(), 20);
+        assert_eq!(BigInt::range(BigInt::new("-3"), BigInt::new("2")).map(|v| format!("{}", v)).collect::<Vec<_>>(),
+                   vec!["-3", "-2", "-1", "0", "1"]);
+        assert_eq!(BigInt::range(BigInt::new("5"), BigInt::new("5")).count(), 0);
+        assert_eq!(BigInt::range(BigInt::new("10"), BigInt::new("-10")).count(), 0);
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        let zero = Self::new("0");
-        if self == zero || rhs == zero {
-            return zero;
-        }
+        let start = BigInt::new("99999999999999999990");
+        let end = BigInt::new("100000000000000000010");
+        let values: Vec<BigInt> = BigInt::range(start.clone(), end.clone()).collect();
+        assert_eq!(BigInt::from(values.len() as u64), end.clone() - start.clone());
+        assert_eq!(values.first(), Some(&start));
+        assert_eq!(values.last(), Some(&(end - BigInt::new("1"))));
+    }
 
-        let one = vec![1];
-        let sign = self.positive == rhs.positive;
-        let mut one_mul: Option<Self> = None;
-        if self.value == one {
-            one_mul = Some(rhs.clone());
-        } else if rhs.value == one {
-            one_mul = Some(self.clone());
-        }
-        if let Some(mut x) = one_mul {
-            x.positive = sign;
-            return x;
-        }
+    #[test]
+    fn test_fibonacci_lucas() {
+        assert_eq!(format!("{}", BigInt::fibonacci(0)), "0".to_string());
+        assert_eq!(format!("{}", BigInt::fibonacci(1)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::fibonacci(2)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::fibonacci(100)), "354224848179261915075".to_string());
+        assert_eq!(BigInt::fibonacci(1000).num_digits(), 209);
+        assert_eq!(format!("{}", BigInt::lucas(0)), "2".to_string());
+        assert_eq!(format!("{}", BigInt::lucas(1)), "1".to_string());
+        assert_eq!(format!("{}", BigInt::lucas(100)), "792070839848372253127".to_string());
 
-        let mut product = Self {
-            positive: sign,
-            value: vec![0; self.value.len() + rhs.value.len()],
-        };
+        let (mut f, mut g) = (BigInt::new("0"), BigInt::new("1"));
+        let (mut l, mut m) = (BigInt::new("2"), BigInt::new("1"));
+        for n in 0..300 {
+            assert_eq!(BigInt::fibonacci(n), f);
+            assert_eq!(BigInt::lucas(n), l);
+            (f, g) = (g.clone(), f + g);
+            (l, m) = (m.clone(), l + m);
+        }
+    }
 
-        for i in (0..self.value.len()).rev() {
-            for j in (0..rhs.value.len()).rev() {
-                let h = i + j;
-                let l = h + 1;
-                let p = self.value[i] * rhs.value[j] + product.value[l];
+    #[test]
+    fn test_bit() {
+        let bits = |v: &str| (0..5).map(|i| BigInt::new(v).bit(i)).collect::<Vec<_>>();
+        assert_eq!(bits("10"), vec![false, true, false, true, false]);
+        assert_eq!(bits("0"), vec![false; 5]);
+        // -10 的补码为 ...10110
+        assert_eq!(bits("-10"), vec![false, true, true, false, true]);
+        assert_eq!(bits("-1"), vec![true; 5]);
+        assert!(!BigInt::new("10").bit(1000));
+        assert!(BigInt::new("-10").bit(1000));
 
-                product.value[l] = p % 10;
-                product.value[h] += p / 10;
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(200);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            for i in [0u64, 1, 7, 8, 63, 64, 150, 199, 200, 300] {
+                assert_eq!(tested.bit(i), v.bit(i));
             }
         }
-
-        product.trim_zero();
-        product
     }
-}
 
-impl ops::Div<BigInt> for BigInt {
-    type Output = Self;
+    #[test]
+    fn test_set_bit() {
+        let mut v = BigInt::new("0");
+        for i in [0u64, 3, 64, 100] {
+            v.set_bit(i, true);
+        }
+        let expected = BigInt::new("2").pow(100) + BigInt::new("2").pow(64) + BigInt::new("9");
+        assert_eq!(v, expected);
+        v.set_bit(3, true);
+        assert_eq!(v, expected);
+        for i in [100u64, 0, 64, 3] {
+            v.set_bit(i, false);
+        }
+        assert!(v.is_zero() && !v.is_negative());
 
-    fn div(self, rhs: Self) -> Self::Output {
-        let zero = Self::new("0");
-        if rhs == zero { panic!("divisor can't be 0") }
-        let one = vec![1];
-        let sign = self.positive == rhs.positive;
-        if rhs.value == one {
-            return Self {
-                positive: sign,
-                value: self.value.clone(),
-            };
+        // -10 的补码为 ...10110
+        let mut v = BigInt::new("-10");
+        v.set_bit(0, true);
+        assert_eq!(format!("{}", v), "-9".to_string());
+        v.set_bit(4, false);
+        assert_eq!(format!("{}", v), "-25".to_string());
+        v.set_bit(200, false);
+        assert_eq!(v, BigInt::new("-25") - BigInt::new("2").pow(200));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let a = rng.gen_bigint(200);
+            let i = rng.gen_range(0..300);
+            let value = rng.gen();
+            let mut tested = BigInt::new(format!("{}", a).as_str());
+            let mut expected = a.clone();
+            tested.set_bit(i, value);
+            expected.set_bit(i, value);
+            assert_eq!(format!("{}", tested), format!("{}", expected));
         }
-        if self.value.len() < rhs.value.len() ||
-            (self.value.len() == rhs.value.len()
-                && self.value < rhs.value) {
-            return zero;
+    }
+
+    #[test]
+    fn test_not_operator() {
+        assert_eq!(format!("{}", !BigInt::new("5")), "-6".to_string());
+        assert_eq!(format!("{}", !BigInt::new("-6")), "5".to_string());
+        assert_eq!(format!("{}", !BigInt::new("0")), "-1".to_string());
+        assert_eq!(format!("{}", !BigInt::new("-1")), "0".to_string());
+        assert!(!(!BigInt::new("-1")).is_negative());
+
+        for a in [-300i64, -256, -1, 0, 1, 255, i64::MIN, i64::MAX] {
+            assert_eq!(format!("{}", !BigInt::from(a)), (!a).to_string());
         }
-        if self.value == rhs.value {
-            return Self {
-                positive: sign,
-                value: one,
-            };
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let a = rng.gen_bigint(300);
+            let tested = BigInt::new(format!("{}", a).as_str());
+            assert_eq!(format!("{}", !tested.clone()), format!("{}", !a));
+            assert_eq!(!!tested.clone(), tested);
         }
-        if !sign || !self.positive {
-            return Self {
-                positive: sign,
-                value: (self.abs() / rhs.abs()).value,
-            };
+    }
+
+    #[test]
+    fn test_div_round() {
+        let round = |a: i64, b: i64, mode| format!("{}", BigInt::from(a).div_round(BigInt::from(b), mode));
+        let cases = [
+            // (a, b, Truncate, Floor, Ceil, HalfUp, HalfEven)
+            (7, 2, "3", "3", "4", "4", "4"),
+            (5, 2, "2", "2", "3", "3", "2"),
+            (-7, 2, "-3", "-4", "-3", "-4", "-4"),
+            (-5, 2, "-2", "-3", "-2", "-3", "-2"),
+            (5, -2, "-2", "-3", "-2", "-3", "-2"),
+            (-5, -2, "2", "2", "3", "3", "2"),
+            (7, 3, "2", "2", "3", "2", "2"),
+            (8, 3, "2", "2", "3", "3", "3"),
+            (-8, 3, "-2", "-3", "-2", "-3", "-3"),
+            (6, 3, "2", "2", "2", "2", "2"),
+            (-6, 3, "-2", "-2", "-2", "-2", "-2"),
+            (1, 4, "0", "0", "1", "0", "0"),
+            (-1, 4, "0", "-1", "0", "0", "0"),
+            (0, 7, "0", "0", "0", "0", "0"),
+        ];
+        for (a, b, truncate, floor, ceil, half_up, half_even) in cases {
+            assert_eq!(round(a, b, RoundingMode::Truncate), truncate);
+            assert_eq!(round(a, b, RoundingMode::Floor), floor);
+            assert_eq!(round(a, b, RoundingMode::Ceil), ceil);
+            assert_eq!(round(a, b, RoundingMode::HalfUp), half_up);
+            assert_eq!(round(a, b, RoundingMode::HalfEven), half_even);
         }
 
-        // 以下 self > rhs > 0
-        let mut quotient = Self {
-            positive: sign,
-            value: vec![],
-        };
+        let a = BigInt::new("-123456789012345678901234567890");
+        let b = BigInt::new("987654321");
+        assert_eq!(a.clone().div_round(b.clone(), RoundingMode::Floor), a.clone().div_floor(b.clone()));
+        assert_eq!(a.clone().div_round(b.clone(), RoundingMode::Ceil), a.div_ceil(b));
+    }
 
-        let mut dividend = self.clone();
-        'outer: loop {
-            let mut diff = Self {
-                positive: true,
-                value: vec![],
-            };
+    #[test]
+    fn test_to_scientific() {
+        assert_eq!(BigInt::new("1234567").to_scientific(3), "1.23e6".to_string());
+        assert_eq!(BigInt::new("1299999").to_scientific(2), "1.2e6".to_string());
+        assert_eq!(BigInt::new("-1234567").to_scientific(1), "-1e6".to_string());
+        assert_eq!(BigInt::new("1000000").to_scientific(3), "1.00e6".to_string());
+        assert_eq!(BigInt::new("7").to_scientific(1), "7e0".to_string());
+        assert_eq!(BigInt::new("-42").to_scientific(5), "-4.2000e1".to_string());
+        assert_eq!(BigInt::new("1234567").to_scientific(7), "1.234567e6".to_string());
+        assert_eq!(BigInt::new("0").to_scientific(3), "0e0".to_string());
+        assert_eq!(BigInt::new("-0").to_scientific(1), "0e0".to_string());
+        assert_eq!(BigInt::new("2").pow(1000).to_scientific(4), "1.071e301".to_string());
+        assert!(catch_unwind(|| BigInt::new("5").to_scientific(0)).is_err());
 
-            // self > rhs ensures existence of diff which > rhs
-            let mut i = 0usize;
-            loop {
-                diff.value.push(dividend.value[i]);
-                i += 1;
-                if diff >= rhs { break; }
-            }
+        for v in [1u64, 9, 10, 12345, 999999, u64::MAX] {
+            assert_eq!(BigInt::from(v).to_scientific(20), format!("{:.19e}", v));
+        }
+    }
 
-            // diff >= rhs first turns true, ensures 1 <= c <= 9
-            let mut c = 1;
-            loop {
-                diff = diff - rhs.clone();
-                if diff < rhs { break; }
-                c += 1;
-            }
-            quotient.value.push(c);
+    #[test]
+    fn test_to_grouped_string() {
+        assert_eq!(BigInt::new("1234567").to_grouped_string(',', 3), "1,234,567".to_string());
+        assert_eq!(BigInt::new("123456").to_grouped_string(',', 3), "123,456".to_string());
+        assert_eq!(BigInt::new("-123456").to_grouped_string(',', 3), "-123,456".to_string());
+        assert_eq!(BigInt::new("-1234").to_grouped_string('_', 3), "-1_234".to_string());
+        assert_eq!(BigInt::new("-123").to_grouped_string(',', 3), "-123".to_string());
+        assert_eq!(BigInt::new("12").to_grouped_string(',', 3), "12".to_string());
+        assert_eq!(BigInt::new("0").to_grouped_string(',', 3), "0".to_string());
+        assert_eq!(BigInt::new("-0").to_grouped_string(',', 1), "0".to_string());
+        assert_eq!(BigInt::new("123456789").to_grouped_string(' ', 4), "1 2345 6789".to_string());
+        assert_eq!(BigInt::new("1234").to_grouped_string('·', 1), "1·2·3·4".to_string());
+        assert!(catch_unwind(|| BigInt::new("5").to_grouped_string(',', 0)).is_err());
 
-            if i >= dividend.value.len() { break; }
+        let v = BigInt::new("-98765432109876543210");
+        assert_eq!(BigInt::new(v.to_grouped_string('_', 3).as_str()), v);
+    }
 
-            let mut rest = Self {
-                positive: true,
-                value: vec![],
-            };
-            if diff == zero {
-                // append zeros
-                while dividend.value[i] == 0 {
-                    quotient.value.push(0);
-                    i += 1;
-                    if i >= dividend.value.len() {
-                        break 'outer;
-                    }
-                }
-            } else {
-                rest.value.extend(&diff.value)
-            }
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(BigInt::from_digits(true, &[1, 2, 3]), BigInt::new("123"));
+        assert_eq!(BigInt::from_digits(false, &[1, 2, 3]), BigInt::new("-123"));
+        assert_eq!(BigInt::from_digits(false, &[0, 0, 4, 0, 5]), BigInt::new("-405"));
+        assert_eq!(format!("{}", BigInt::from_digits(true, &[9; 25])), "9".repeat(25));
 
-            // append zeros
-            loop {
-                rest.value.push(dividend.value[i]);
-                i += 1;
-                if rest < rhs {
-                    quotient.value.push(0);
-                } else {
-                    break;
-                }
-                if i >= dividend.value.len() {
-                    break 'outer;
-                }
-            }
+        for digits in [&[0u8, 0, 0][..], &[0], &[]] {
+            let zero = BigInt::from_digits(false, digits);
+            assert!(zero.is_zero() && !zero.is_negative());
+            assert_eq!(format!("{}", zero), "0".to_string());
+        }
 
-            rest.value.extend(&dividend.value[i..]);
+        assert!(catch_unwind(|| BigInt::from_digits(true, &[1, 10])).is_err());
+    }
 
-            if rest < rhs { break; }
+    #[test]
+    fn test_into_parts() {
+        assert_eq!(BigInt::new("-1203").into_parts(), (false, vec![1, 2, 0, 3]));
+        assert_eq!(BigInt::new("-0").into_parts(), (true, vec![0]));
 
-            dividend = rest;
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = BigInt::new(format!("{}", rng.gen_bigint(300)).as_str());
+            let (positive, digits) = v.clone().into_parts();
+            let digits: Vec<u8> = digits.into_iter().map(|d| d as u8).collect();
+            assert_eq!(BigInt::from_digits(positive, &digits), v);
         }
-
-        quotient
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::panic::catch_unwind;
+    #[test]
+    fn test_add_sub_carry_chains() {
+        assert_eq!(format!("{}", BigInt::new("1000000") - BigInt::new("1")), "999999".to_string());
+        assert_eq!(format!("{}", BigInt::new("1000001") - BigInt::new("2")), "999999".to_string());
+        assert_eq!(format!("{}", BigInt::new("10") - BigInt::new("9")), "1".to_string());
+        assert_eq!(format!("{}", BigInt::new("-1000000") + BigInt::new("1")), "-999999".to_string());
 
-    use num_bigint::{RandBigInt, ToBigInt};
+        for n in 1..60 {
+            let power = BigInt::new("1").shift_decimal_left(n);
+            let nines = BigInt::new("9".repeat(n).as_str());
+            let one = BigInt::new("1");
+            assert_eq!(power.clone() - one.clone(), nines);
+            assert_eq!(power.clone() - nines.clone(), one);
+            assert_eq!(nines.clone() + one.clone(), power);
+            assert_eq!(nines.clone() + nines.clone(), power.clone() + power.clone() - BigInt::new("2"));
+            assert_eq!(-power.clone() + nines.clone(), -one.clone());
 
-    use super::*;
+            let mut v = nines.clone();
+            v += one.clone();
+            assert_eq!(v, power);
+            v -= one;
+            assert_eq!(v, nines);
+        }
+    }
 
     #[test]
-    fn test_integer_format() {
-        assert!(catch_unwind(|| BigInt::new("a")).is_err());
-        assert!(catch_unwind(|| BigInt::new("1234+")).is_err());
-        assert!(catch_unwind(|| BigInt::new("--1234")).is_err());
-        assert!(catch_unwind(|| BigInt::new("++1234")).is_err());
-        assert!(catch_unwind(|| BigInt::new("+12.34")).is_err());
+    fn test_count_ones() {
+        for v in [0u64, 1, 2, 3, 10, 255, 256, 12345, u32::MAX as u64, u64::MAX] {
+            assert_eq!(BigInt::from(v).count_ones(), v.count_ones() as u64);
+            assert_eq!((-BigInt::from(v)).count_ones(), v.count_ones() as u64);
+        }
+        assert_eq!((BigInt::new("2").pow(1000) - BigInt::new("1")).count_ones(), 1000);
+        assert_eq!((BigInt::new("2").pow(1000) + BigInt::new("2").pow(100)).count_ones(), 2);
 
-        assert_eq!(format!("{}", BigInt::new("1234")), "1234".to_string());
-        assert_eq!(format!("{}", BigInt::new("+1234")), "1234".to_string());
-        assert_eq!(format!("{}", BigInt::new("-1234")), "-1234".to_string());
-        assert_eq!(format!("{}", BigInt::new("001234")), "1234".to_string());
-        assert_eq!(format!("{}", BigInt::new("+001234")), "1234".to_string());
-        assert_eq!(format!("{}", BigInt::new("-001234")), "-1234".to_string());
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v: u64 = rng.gen();
+            assert_eq!(BigInt::from(v).count_ones(), v.count_ones() as u64);
+        }
     }
 
     #[test]
-    fn test_comparator() {
-        assert!(BigInt::new("0") == BigInt::new("0"));
-        assert!(BigInt::new("0") != BigInt::new("1"));
-        assert!(BigInt::new("1") != -BigInt::new("1"));
-        assert!(BigInt::new("1") < BigInt::new("2"));
-        assert!(BigInt::new("1") <= BigInt::new("1"));
-        assert!(BigInt::new("2") > BigInt::new("1"));
-        assert!(BigInt::new("2") >= BigInt::new("2"));
-        assert!(BigInt::new("10") > BigInt::new("9"));
+    fn test_u32_limbs() {
+        assert_eq!(BigInt::new("0").to_u32_limbs(), Vec::<u32>::new());
+        assert_eq!(BigInt::new("-4294967296").to_u32_limbs(), vec![0, 1]);
+        assert_eq!(BigInt::new("4294967295").to_u32_limbs(), vec![u32::MAX]);
+        assert_eq!(BigInt::from_u32_limbs(false, &[5, 1]), BigInt::new("-4294967301"));
+        assert_eq!(BigInt::from_u32_limbs(true, &[7, 0, 0]), BigInt::new("7"));
+        assert!(!BigInt::from_u32_limbs(false, &[0, 0]).is_negative());
+        assert!(BigInt::from_u32_limbs(false, &[]).is_zero());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v = rng.gen_bigint(500);
+            let tested = BigInt::new(format!("{}", v).as_str());
+            let limbs = tested.to_u32_limbs();
+            assert_eq!(limbs, v.to_u32_digits().1);
+            assert_eq!(BigInt::from_u32_limbs(!tested.is_negative(), &limbs), tested);
+        }
     }
 
     #[test]
-    fn test_ng_operator() {
-        assert!(BigInt::new("100") != -BigInt::new("100"));
-        assert_eq!(format!("{}", -BigInt::new("100")), "-100".to_string());
+    fn test_wrapping_to_bits() {
+        for v in [-70000i64, -300, -256, -129, -128, -1, 0, 1, 127, 128, 255, 256, 300, 70000] {
+            let tested = BigInt::from(v);
+            assert_eq!(tested.wrapping_to_bits(8), BigInt::from(v as u8 as i64));
+            assert_eq!(tested.wrapping_signed_to_bits(8), BigInt::from(v as i8 as i64));
+            assert_eq!(tested.wrapping_to_bits(16), BigInt::from(v as u16 as i64));
+            assert_eq!(tested.wrapping_signed_to_bits(16), BigInt::from(v as i16 as i64));
+        }
+        assert_eq!(BigInt::from(u64::MAX).wrapping_signed_to_bits(64), BigInt::from(-1));
+        assert_eq!(BigInt::from(-1).wrapping_to_bits(64), BigInt::from(u64::MAX));
+        assert_eq!(BigInt::from(i128::MAX).wrapping_signed_to_bits(32), BigInt::from(-1));
+        assert_eq!(BigInt::from(5).wrapping_to_bits(0), BigInt::from(0));
+        assert_eq!(BigInt::from(3).wrapping_signed_to_bits(1), BigInt::from(-1));
+        assert!(catch_unwind(|| BigInt::from(5).wrapping_signed_to_bits(0)).is_err());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let v: i128 = rng.gen();
+            let tested = BigInt::from(v);
+            assert_eq!(tested.wrapping_to_bits(32), BigInt::from(v as u32));
+            assert_eq!(tested.wrapping_signed_to_bits(32), BigInt::from(v as i32));
+            assert_eq!(tested.wrapping_to_bits(64), BigInt::from(v as u64));
+            assert_eq!(tested.wrapping_signed_to_bits(64), BigInt::from(v as i64));
+        }
     }
 
+    #[test]
+    fn test_digit_sum() {
+        assert_eq!(BigInt::new("12345").digit_sum(), 15);
+        assert_eq!(BigInt::new("-12345").digit_sum(), 15);
+        assert_eq!(BigInt::new("0").digit_sum(), 0);
+        assert_eq!(BigInt::new("9".repeat(1000).as_str()).digit_sum(), 9000);
+        // 2^100 = 1267650600228229401496703205376
+        assert_eq!(BigInt::new("2").pow(100).digit_sum(), 115);
 
-    fn test_operator(f: fn(BigInt, BigInt, num_bigint::BigInt, num_bigint::BigInt)
-                           -> (BigInt, num_bigint::BigInt)) {
+        // 弃九法：数字和与原数模 9 同余
         let mut rng = rand::thread_rng();
-        let low = -10000.to_bigint().unwrap();
-        let high = 10000.to_bigint().unwrap();
+        for _ in 0..50 {
+            let v = BigInt::new(format!("{}", rng.gen_biguint(300)).as_str());
+            assert_eq!(BigInt::from(v.digit_sum()).reduce_mod(&BigInt::new("9")), v.reduce_mod(&BigInt::new("9")));
+        }
+    }
 
-        for _ in 0..1000 {
-            let a = rng.gen_bigint(1000);
-            let b = rng.gen_bigint_range(&low, &high);
-            let a_string = format!("{}", a);
-            let b_string = format!("{}", b);
+    #[test]
+    fn test_from_raw_parts() {
+        // SAFETY: 以下均为规范化的各位
+        let v = unsafe { BigInt::from_raw_parts(false, vec![1, 2, 0, 3]) };
+        assert_eq!(v, BigInt::new("-1203"));
+        let zero = unsafe { BigInt::from_raw_parts(true, vec![0]) };
+        assert_eq!(zero, BigInt::new("0"));
 
-            let (ret, expected_ret) = f(
-                BigInt::new(a_string.as_str()),
-                BigInt::new(b_string.as_str()),
-                a,
-                b,
-            );
-            assert_eq!(format!("{}", ret), format!("{}", expected_ret));
+        let expected = BigInt::new("-98765432109876543210");
+        let (positive, value) = expected.clone().into_parts();
+        assert_eq!(unsafe { BigInt::from_raw_parts(positive, value) }, expected);
+
+        if cfg!(debug_assertions) {
+            assert!(catch_unwind(|| unsafe { BigInt::from_raw_parts(true, vec![0, 5]) }).is_err());
+            assert!(catch_unwind(|| unsafe { BigInt::from_raw_parts(true, vec![1, 10]) }).is_err());
+            assert!(catch_unwind(|| unsafe { BigInt::from_raw_parts(false, vec![0]) }).is_err());
+            assert!(catch_unwind(|| unsafe { BigInt::from_raw_parts(true, vec![]) }).is_err());
         }
     }
 
     #[test]
-    fn test_add_operator() {
-        test_operator(|tested_a, tested_b, a, b|
-            (tested_a + tested_b, a + b)
-        );
+    fn test_normalize() {
+        let mut v = BigInt { positive: false, value: vec![5, 0, 0] };
+        v.normalize();
+        assert_eq!(v.value, vec![5]);
+        assert_eq!(format!("{}", v), "-5".to_string());
+
+        for value in [vec![0, 0, 0], vec![0], vec![]] {
+            let mut zero = BigInt { positive: false, value };
+            zero.normalize();
+            assert_eq!(zero.value, vec![0]);
+            assert!(zero.positive);
+            assert_eq!(zero, BigInt::new("0"));
+        }
+
+        let mut v = BigInt::new("-1203");
+        v.normalize();
+        assert_eq!(v, BigInt::new("-1203"));
     }
 
     #[test]
-    fn test_sub_operator() {
-        test_operator(|tested_a, tested_b, a, b|
-            (tested_a - tested_b, a - b)
-        );
+    fn test_checked_sub_nonneg() {
+        let sub = |a: &str, b: &str| BigInt::new(a).checked_sub_nonneg(BigInt::new(b));
+        assert_eq!(sub("5", "3"), Some(BigInt::new("2")));
+        assert_eq!(sub("3", "5"), None);
+        assert_eq!(sub("5", "5"), Some(BigInt::new("0")));
+        assert_eq!(sub("0", "0"), Some(BigInt::new("0")));
+        assert_eq!(sub("0", "1"), None);
+        assert_eq!(sub("100000000000000000000", "1"), Some(BigInt::new("99999999999999999999")));
+        assert_eq!(sub("99999999999999999999", "100000000000000000000"), None);
     }
 
     #[test]
-    fn test_mul_operator() {
-        test_operator(|tested_a, tested_b, a, b|
-            (tested_a * tested_b, a * b)
-        );
+    fn test_eq_str() {
+        let v = BigInt::new("-123");
+        assert_eq!(v, "-123");
+        assert_eq!(v, *"-123");
+        assert_eq!(v, "-0_123");
+        assert_ne!(v, "123");
+        assert_ne!(v, "-124");
+        assert_ne!(v, "");
+        assert_ne!(v, "-");
+        assert_ne!(v, "-12a");
+        assert_ne!(v, " -123");
+        assert_eq!(BigInt::new("0"), "-0");
+        assert_eq!(BigInt::new("2").pow(100), "1267650600228229401496703205376");
     }
 
     #[test]
-    #[should_panic]
-    fn test_div_zero() {
-        let _ = BigInt::new("100") / BigInt::new("0");
+    fn test_pow10() {
+        assert_eq!(BigInt::pow10(0), BigInt::new("1"));
+        assert_eq!(BigInt::pow10(1), BigInt::new("10"));
+        assert_eq!(BigInt::pow10(3), BigInt::new("1000"));
+        for exp in [5usize, 19, 20, 100, 1000] {
+            assert_eq!(BigInt::pow10(exp), BigInt::new("10").pow(exp as u32));
+            assert_eq!(BigInt::pow10(exp).num_digits(), exp + 1);
+        }
     }
 
     #[test]
-    fn test_div_operator() {
-        test_operator(|tested_a, tested_b, a, b|
-            (tested_a / tested_b, a / b)
-        );
+    fn test_add_mul() {
+        let add_mul = |x: &str, a: &str, b: &str| format!("{}", BigInt::new(x).add_mul(&BigInt::new(a), &BigInt::new(b)));
+        assert_eq!(add_mul("1", "2", "3"), "7".to_string());
+        assert_eq!(add_mul("-10", "2", "5"), "0".to_string());
+        assert_eq!(add_mul("10", "-2", "3"), "4".to_string());
+        assert_eq!(add_mul("5", "0", "123456789"), "5".to_string());
+        assert_eq!(add_mul("0", "-99999999999", "99999999999"), "-9999999999800000000001".to_string());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let x = BigInt::new(format!("{}", rng.gen_bigint(400)).as_str());
+            let a = BigInt::new(format!("{}", rng.gen_bigint(200)).as_str());
+            let b = BigInt::new(format!("{}", rng.gen_bigint(200)).as_str());
+            assert_eq!(x.clone().add_mul(&a, &b), x + a.clone() * b.clone());
+        }
+    }
+
+    #[test]
+    fn test_eval_poly() {
+        let coeffs = |cs: &[i64]| cs.iter().map(|&c| BigInt::from(c)).collect::<Vec<_>>();
+        // 3 - 2x + x^2
+        let p = coeffs(&[3, -2, 1]);
+        for (x, expected) in [(0, 3), (1, 2), (2, 3), (-3, 18), (10, 83)] {
+            assert_eq!(BigInt::eval_poly(&p, &BigInt::from(x)), BigInt::from(expected));
+        }
+        assert_eq!(BigInt::eval_poly(&[], &BigInt::from(5)), BigInt::new("0"));
+        assert_eq!(BigInt::eval_poly(&coeffs(&[-7]), &BigInt::from(5)), BigInt::new("-7"));
+        assert_eq!(BigInt::eval_poly(&coeffs(&[0, 0, 0, 1]), &BigInt::new("-100000000000")),
+                   BigInt::new("-1000000000000000000000000000000000"));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let p: Vec<BigInt> = (0..rng.gen_range(1..10))
+                .map(|_| BigInt::new(format!("{}", rng.gen_bigint(100)).as_str()))
+                .collect();
+            let x = BigInt::new(format!("{}", rng.gen_bigint(64)).as_str());
+            let expected: BigInt = p.iter()
+                .enumerate()
+                .map(|(i, c)| c.clone() * x.clone().pow(i as u32))
+                .sum();
+            assert_eq!(BigInt::eval_poly(&p, &x), expected);
+        }
     }
 }